@@ -2,21 +2,40 @@
 
 use crate::{
     auth::{AuthBuilder, AuthConfig},
-    config::{ClientConfig, MediaType, SearchOptions},
+    clock::Clock,
+    config::{ChartOptions, ClientConfig, MediaType, QueryOptions, SearchOptions},
+    credentials::{CredentialStore, StoredToken, TokenKind},
     error::{AppleMusicError, Result},
-    http::HttpClient,
-    models::{catalog::*, common::*, library::*, search::*},
+    http::{HttpClient, UserTokenRefreshFuture},
+    models::{catalog::*, charts::*, common::*, library::*, search::*},
+    secret::Secret,
+    storefronts::StorefrontRegistry,
     utils::SearchParamsBuilder,
 };
-use std::sync::Arc;
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex, RwLock, Weak};
 use tokio::sync::Mutex;
 
+/// Maximum number of values in a single `filter[...]` query parameter
+const MAX_FILTER_VALUES: usize = 25;
+
+/// Stream of resources fetched page by page
+pub type ResourceStream<'a, T> = Pin<Box<dyn Stream<Item = Result<T>> + Send + 'a>>;
+
 /// Main Apple Music API client
 #[derive(Clone)]
 pub struct AppleMusicClient {
     http_client: Arc<HttpClient>,
     auth: Arc<Mutex<AuthBuilder>>,
     config: ClientConfig,
+    credential_store: Option<Arc<dyn CredentialStore>>,
+    storefront: Arc<RwLock<String>>,
+    language: Option<String>,
+    supported_languages: Arc<StdMutex<HashMap<String, Vec<String>>>>,
 }
 
 impl AppleMusicClient {
@@ -29,40 +48,59 @@ impl AppleMusicClient {
         let auth =
             AuthBuilder::Simple(crate::auth::SimpleAuth::new(config.developer_token.clone()));
 
-        let mut http_client = HttpClient::new(&config)?;
-        if let Some(user_token) = &config.user_token {
-            http_client.set_user_token(Some(user_token.clone()));
-        }
-
-        Ok(Self {
-            http_client: Arc::new(http_client),
-            auth: Arc::new(Mutex::new(auth)),
-            config,
-        })
+        let http_client = HttpClient::new(&config)?;
+        Self::from_parts(config, auth, http_client, None).await
     }
 
     /// Create a client with JWT authentication
+    ///
+    /// The developer token is signed again before a request once it expires
+    /// within 24 hours, so long-running clients keep working.
     pub async fn with_jwt_auth(
         config: ClientConfig,
         team_id: String,
         key_id: String,
         private_key: String,
+    ) -> Result<Self> {
+        Self::jwt_client(config, team_id, key_id, private_key, None).await
+    }
+
+    /// Create a client with JWT authentication that saves its tokens to a credential store
+    ///
+    /// Works like [`with_credential_store`](Self::with_credential_store),
+    /// and every developer token signed again before it expires is saved to
+    /// the store as well.
+    pub async fn with_jwt_auth_and_credential_store(
+        mut config: ClientConfig,
+        team_id: String,
+        key_id: String,
+        private_key: String,
+        store: Arc<dyn CredentialStore>,
+    ) -> Result<Self> {
+        load_stored_user_token(&mut config, store.as_ref())?;
+        Self::jwt_client(config, team_id, key_id, private_key, Some(store)).await
+    }
+
+    /// Create a client signing its own developer tokens
+    async fn jwt_client(
+        config: ClientConfig,
+        team_id: String,
+        key_id: String,
+        private_key: String,
+        store: Option<Arc<dyn CredentialStore>>,
     ) -> Result<Self> {
         config.validate()?;
 
         let auth_config = AuthConfig::jwt(team_id, key_id, private_key);
-        let auth = auth_config.build()?;
-
-        let mut http_client = HttpClient::new(&config)?;
-        if let Some(user_token) = &config.user_token {
-            http_client.set_user_token(Some(user_token.clone()));
+        let mut auth = auth_config.build_with_clock(config.clock.clone())?;
+        let developer_token = Secret::new(auth.developer_token()?);
+        if let Some(store) = &store {
+            save_developer_token(store.as_ref(), &developer_token)?;
         }
 
-        Ok(Self {
-            http_client: Arc::new(http_client),
-            auth: Arc::new(Mutex::new(auth)),
-            config,
-        })
+        let http_client = HttpClient::new(&config)?;
+        http_client.set_developer_token(developer_token);
+        Self::from_parts(config, auth, http_client, store).await
     }
 
     /// Create a client with JWT authentication from private key file
@@ -78,35 +116,238 @@ impl AppleMusicClient {
         let auth =
             AuthBuilder::Simple(crate::auth::SimpleAuth::new(config.developer_token.clone()));
 
-        let mut http_client = HttpClient::new(&config)?;
-        if let Some(user_token) = &config.user_token {
-            http_client.set_user_token(Some(user_token.clone()));
-        }
+        let http_client = HttpClient::new(&config)?;
+        Self::from_parts(config, auth, http_client, None).await
+    }
 
-        Ok(Self {
+    /// Assemble a client, shared by every constructor
+    ///
+    /// Registers the developer token refresher for JWT authentication, which
+    /// saves refreshed tokens to the credential store, and follows the user's
+    /// storefront if the configuration asks for it.
+    async fn from_parts(
+        config: ClientConfig,
+        auth: AuthBuilder,
+        http_client: HttpClient,
+        credential_store: Option<Arc<dyn CredentialStore>>,
+    ) -> Result<Self> {
+        let signs_tokens = matches!(auth, AuthBuilder::Jwt(_));
+        let mut client = Self {
             http_client: Arc::new(http_client),
             auth: Arc::new(Mutex::new(auth)),
+            storefront: Arc::new(RwLock::new(config.storefront.clone())),
+            language: config.language.clone(),
             config,
-        })
+            credential_store,
+            supported_languages: Arc::default(),
+        };
+
+        if signs_tokens {
+            // Re-sign the developer token before it expires
+            let auth = client.auth.clone();
+            let store = client.credential_store.clone();
+            client
+                .http_client
+                .set_developer_token_refresher(Some(Arc::new(move || {
+                    let auth = auth.clone();
+                    let store = store.clone();
+                    Box::pin(async move {
+                        let token = Secret::new(auth.lock().await.developer_token()?);
+                        if let Some(store) = &store {
+                            save_developer_token(store.as_ref(), &token)?;
+                        }
+                        Ok(token)
+                    })
+                })));
+        }
+
+        client.sync_user_storefront().await?;
+        Ok(client)
+    }
+
+    /// Create a client that loads and saves its tokens through a credential store
+    ///
+    /// If the configuration has no user token, a stored one is used. The
+    /// developer token, which must be a JWT, is saved to the store, and later calls to
+    /// [`set_user_token`](Self::set_user_token) update the stored user token.
+    ///
+    /// Use [`with_jwt_auth_and_credential_store`](Self::with_jwt_auth_and_credential_store)
+    /// to have the client sign, and save, its own developer tokens.
+    pub async fn with_credential_store(
+        mut config: ClientConfig,
+        store: Arc<dyn CredentialStore>,
+    ) -> Result<Self> {
+        config.validate()?;
+        load_stored_user_token(&mut config, store.as_ref())?;
+        save_developer_token(store.as_ref(), &config.developer_token)?;
+
+        let auth =
+            AuthBuilder::Simple(crate::auth::SimpleAuth::new(config.developer_token.clone()));
+        let http_client = HttpClient::new(&config)?;
+        Self::from_parts(config, auth, http_client, Some(store)).await
     }
 
     /// Set the user token for personalized requests
+    ///
+    /// When the client has a credential store, the token is saved to it (or
+    /// removed from it when `None`). With
+    /// [`use_user_storefront`](ClientConfig::use_user_storefront), catalog
+    /// requests switch to the user's storefront, and back to the configured
+    /// one when the token is removed.
     pub async fn set_user_token(&mut self, user_token: Option<String>) -> Result<()> {
         self.auth.lock().await.set_user_token(user_token.clone());
-        Arc::get_mut(&mut self.http_client)
-            .ok_or_else(|| AppleMusicError::config("Cannot modify HTTP client while in use"))?
-            .set_user_token(user_token);
+        self.http_client
+            .set_user_token(user_token.clone().map(Secret::new));
+
+        if let Some(store) = &self.credential_store {
+            match user_token {
+                Some(token) => store.save(
+                    TokenKind::User,
+                    &StoredToken::new(token, self.config.clock.now_utc(), None),
+                )?,
+                None => store.clear(TokenKind::User)?,
+            }
+        }
+
+        self.sync_user_storefront().await
+    }
+
+    /// Follow the user's storefront if the configuration asks for it
+    async fn sync_user_storefront(&mut self) -> Result<()> {
+        if !self.config.use_user_storefront {
+            return Ok(());
+        }
+
+        if self.http_client.has_user_token() {
+            self.use_user_storefront().await?;
+        } else {
+            self.set_storefront(self.config.storefront.clone());
+        }
         Ok(())
     }
 
+    /// Switch catalog requests to the user's storefront
+    ///
+    /// Returns the new storefront ID. Requires a user token.
+    pub async fn use_user_storefront(&mut self) -> Result<String> {
+        let storefront = self.get_user_storefront().await?;
+        self.set_storefront(storefront.id.clone());
+        Ok(storefront.id)
+    }
+
     /// Get the current user token
     pub async fn user_token(&self) -> Option<String> {
-        self.auth.lock().await.user_token().map(|s| s.to_string())
+        self.http_client
+            .user_token()
+            .map(|user_token| user_token.expose().to_string())
     }
 
     /// Check if user token is available
     pub async fn has_user_token(&self) -> bool {
-        self.auth.lock().await.has_user_token()
+        self.http_client.has_user_token()
+    }
+
+    /// Register a hook that provides a new user token when Apple rejects the current one
+    ///
+    /// When a request fails with [`AppleMusicError::UserTokenExpired`], the
+    /// hook is called once and the request is retried with the returned
+    /// token. If the retry fails as well, its error is returned. The new
+    /// token is saved to the credential store, if any, and with
+    /// [`use_user_storefront`](ClientConfig::use_user_storefront) later
+    /// catalog requests follow the storefront of the new token.
+    pub fn set_user_token_refresher<F, Fut>(&self, refresher: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        let refresher = Arc::new(refresher);
+        let store = self.credential_store.clone();
+        let clock = self.config.clock.clone();
+        // The hook lives in the HTTP client, so it must not keep it alive
+        let http_client = Arc::downgrade(&self.http_client);
+        let storefront = self
+            .config
+            .use_user_storefront
+            .then(|| self.storefront.clone());
+
+        self.http_client.set_user_token_refresher(Some(Arc::new(
+            move || -> UserTokenRefreshFuture {
+                let refresher = refresher.clone();
+                let store = store.clone();
+                let clock = clock.clone();
+                let http_client = http_client.clone();
+                let storefront = storefront.clone();
+                Box::pin(async move {
+                    let user_token = refresher().await?;
+                    if let Some(store) = store {
+                        store.save(
+                            TokenKind::User,
+                            &StoredToken::new(user_token.clone(), clock.now_utc(), None),
+                        )?;
+                    }
+                    if let (Some(storefront), Some(http_client)) =
+                        (storefront, Weak::upgrade(&http_client))
+                    {
+                        let user_storefront =
+                            Self::storefront_for_user_token(&http_client, &user_token).await?;
+                        *storefront.write().unwrap_or_else(|e| e.into_inner()) = user_storefront.id;
+                    }
+                    Ok(user_token)
+                })
+            },
+        )));
+    }
+
+    /// Remove the user token refresh hook
+    pub fn clear_user_token_refresher(&self) {
+        self.http_client.set_user_token_refresher(None);
+    }
+
+    /// Register an additional signing key
+    ///
+    /// Only available for clients created with [`with_jwt_auth`](Self::with_jwt_auth).
+    pub async fn add_signing_key(&self, key_id: String, private_key: String) -> Result<()> {
+        let mut auth = self.auth.lock().await;
+        let manager = auth.jwt_manager()?;
+        manager.add_key(key_id, private_key)?;
+
+        // Replacing the active key invalidates the current token
+        let developer_token = Secret::new(manager.get_developer_token()?);
+        self.http_client.set_developer_token(developer_token);
+        Ok(())
+    }
+
+    /// Switch the signing key used for developer tokens
+    ///
+    /// Subsequent requests carry a token signed with the new key. Requests
+    /// already sent keep using the token they were sent with.
+    pub async fn set_active_signing_key(&self, key_id: &str) -> Result<()> {
+        let mut auth = self.auth.lock().await;
+        let manager = auth.jwt_manager()?;
+        manager.set_active_key(key_id)?;
+
+        let developer_token = Secret::new(manager.get_developer_token()?);
+        self.http_client.set_developer_token(developer_token);
+        Ok(())
+    }
+
+    /// Remove a signing key that is no longer active
+    pub async fn remove_signing_key(&self, key_id: &str) -> Result<()> {
+        self.auth.lock().await.jwt_manager()?.remove_key(key_id)
+    }
+
+    /// Start a localhost server that captures a user token through MusicKit JS
+    ///
+    /// Open [`UserTokenServer::url`] in a browser, then await
+    /// [`UserTokenServer::wait_for_token`] and pass the result to
+    /// [`set_user_token`](Self::set_user_token).
+    #[cfg(feature = "user-token-server")]
+    pub async fn user_token_server(
+        &self,
+        port: u16,
+    ) -> Result<crate::user_token_server::UserTokenServer> {
+        crate::user_token_server::UserTokenServer::bind(self.http_client.developer_token(), port)
+            .await
     }
 
     // ===== CATALOG API METHODS =====
@@ -139,10 +380,12 @@ impl AppleMusicClient {
             .into_iter()
             .map(|(k, v)| (k.to_string(), v))
             .collect();
+        let path = format!("v1/catalog/{}/search", self.storefront());
         let response: SearchResponse = self
             .http_client
-            .request("v1/catalog/{storefront}/search")
+            .request(&path)
             .query_params(query_params)
+            .query_params(self.language_params(options.language.as_deref()).await?)
             .get_json()
             .await?;
 
@@ -151,10 +394,1432 @@ impl AppleMusicClient {
 
     /// Get an album by ID
     pub async fn get_album(&self, id: &str) -> Result<Album> {
+        self.get_album_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an album by ID with query options, e.g. to include relationships
+    pub async fn get_album_with_options(&self, id: &str, options: &QueryOptions) -> Result<Album> {
+        self.get_catalog_resource("albums", id, "Album not found", options)
+            .await
+    }
+
+    /// Get every track of an album, following all pages
+    pub async fn get_album_tracks(&self, id: &str) -> Result<Vec<Song>> {
+        self.get_album_tracks_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every track of an album with query options, following all pages
+    pub async fn get_album_tracks_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Song>> {
+        self.get_catalog_related("albums", id, "tracks", options)
+            .await
+    }
+
+    /// Get an album with its `tracks` relationship filled completely
+    pub async fn get_album_with_tracks(&self, id: &str) -> Result<Album> {
+        self.get_album_with_tracks_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an album with query options and its `tracks` relationship filled completely
+    ///
+    /// `tracks` is added to the relationships in `options.include`.
+    pub async fn get_album_with_tracks_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Album> {
+        crate::utils::validate_resource_id(id)?;
+
+        let path = format!("v1/catalog/{}/albums/{}", self.storefront(), id);
+        let mut params = self.catalog_params(options).await?;
+        add_include(&mut params, "tracks");
+        let response: ApiResponse<Album> = self
+            .http_client
+            .request(&path)
+            .query_params(params)
+            .get_json()
+            .await?;
+
+        let mut album = response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppleMusicError::Api {
+                status: 404,
+                message: "Album not found".to_string(),
+            })?;
+
+        let track_options = tracks_options(options);
+        let relationships = album.relationships.get_or_insert_with(Default::default);
+        let tracks = relationships.tracks.get_or_insert_with(Default::default);
+        if let Some(next) = tracks.next.take() {
+            let remaining: Vec<Song> = self
+                .get_all_pages(&next, self.catalog_params(&track_options).await?)
+                .await?;
+            tracks.data.extend(remaining);
+        } else if tracks.data.is_empty() {
+            // The relationship was not included, fetch it separately
+            tracks.data = self
+                .get_album_tracks_with_options(id, &track_options)
+                .await?;
+        }
+
+        Ok(album)
+    }
+
+    /// Get an artist by ID
+    pub async fn get_artist(&self, id: &str) -> Result<Artist> {
+        self.get_artist_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an artist by ID with query options, e.g. to include relationships
+    pub async fn get_artist_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Artist> {
+        self.get_catalog_resource("artists", id, "Artist not found", options)
+            .await
+    }
+
+    /// Get every album of an artist
+    pub async fn get_artist_albums(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_artist_albums_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every album of an artist with query options
+    pub async fn get_artist_albums_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("artists", id, "albums", options)
+            .await
+    }
+
+    /// Get every music video of an artist
+    pub async fn get_artist_music_videos(&self, id: &str) -> Result<Vec<MusicVideo>> {
+        self.get_artist_music_videos_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every music video of an artist with query options
+    pub async fn get_artist_music_videos_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<MusicVideo>> {
+        self.get_catalog_related("artists", id, "music-videos", options)
+            .await
+    }
+
+    /// Get every playlist of an artist
+    pub async fn get_artist_playlists(&self, id: &str) -> Result<Vec<Playlist>> {
+        self.get_artist_playlists_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every playlist of an artist with query options
+    pub async fn get_artist_playlists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Playlist>> {
+        self.get_catalog_related("artists", id, "playlists", options)
+            .await
+    }
+
+    /// Get the genres of an artist
+    pub async fn get_artist_genres(&self, id: &str) -> Result<Vec<Genre>> {
+        self.get_artist_genres_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the genres of an artist with query options
+    pub async fn get_artist_genres_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Genre>> {
+        self.get_catalog_related("artists", id, "genres", options)
+            .await
+    }
+
+    /// Get an artist's top songs
+    pub async fn get_artist_top_songs(&self, id: &str) -> Result<Vec<Song>> {
+        self.get_artist_top_songs_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an artist's top songs with query options
+    pub async fn get_artist_top_songs_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Song>> {
+        self.get_catalog_related("artists", id, "view/top-songs", options)
+            .await
+    }
+
+    /// Get an artist's latest release
+    pub async fn get_artist_latest_release(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_artist_latest_release_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an artist's latest release with query options
+    pub async fn get_artist_latest_release_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("artists", id, "view/latest-release", options)
+            .await
+    }
+
+    /// Get artists similar to an artist
+    pub async fn get_artist_similar_artists(&self, id: &str) -> Result<Vec<Artist>> {
+        self.get_artist_similar_artists_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get artists similar to an artist with query options
+    pub async fn get_artist_similar_artists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Artist>> {
+        self.get_catalog_related("artists", id, "view/similar-artists", options)
+            .await
+    }
+
+    /// Get playlists featuring an artist
+    pub async fn get_artist_featured_playlists(&self, id: &str) -> Result<Vec<Playlist>> {
+        self.get_artist_featured_playlists_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get playlists featuring an artist with query options
+    pub async fn get_artist_featured_playlists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Playlist>> {
+        self.get_catalog_related("artists", id, "view/featured-playlists", options)
+            .await
+    }
+
+    /// Get an artist's full-length albums
+    pub async fn get_artist_full_albums(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_artist_full_albums_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an artist's full-length albums with query options
+    pub async fn get_artist_full_albums_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("artists", id, "view/full-albums", options)
+            .await
+    }
+
+    /// Get an artist's singles
+    pub async fn get_artist_singles(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_artist_singles_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an artist's singles with query options
+    pub async fn get_artist_singles_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("artists", id, "view/singles", options)
+            .await
+    }
+
+    /// Get compilation albums featuring an artist
+    pub async fn get_artist_compilation_albums(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_artist_compilation_albums_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get compilation albums featuring an artist with query options
+    pub async fn get_artist_compilation_albums_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("artists", id, "view/compilation-albums", options)
+            .await
+    }
+
+    /// Get a song by ID
+    pub async fn get_song(&self, id: &str) -> Result<Song> {
+        self.get_song_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a song by ID with query options, e.g. to include relationships
+    pub async fn get_song_with_options(&self, id: &str, options: &QueryOptions) -> Result<Song> {
+        self.get_catalog_resource("songs", id, "Song not found", options)
+            .await
+    }
+
+    /// Get a playlist by ID
+    pub async fn get_catalog_playlist(&self, id: &str) -> Result<Playlist> {
+        self.get_catalog_playlist_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a catalog playlist by ID with query options, e.g. to include relationships
+    pub async fn get_catalog_playlist_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Playlist> {
+        self.get_catalog_resource("playlists", id, "Playlist not found", options)
+            .await
+    }
+
+    /// Get a library playlist by ID
+    pub async fn get_library_playlist(&self, id: &str) -> Result<Playlist> {
+        self.get_library_playlist_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a library playlist by ID with its tracks included
+    pub async fn get_library_playlist_with_tracks(&self, id: &str) -> Result<Playlist> {
+        self.get_library_playlist_with_options(id, &QueryOptions::new().with_include(&["tracks"]))
+            .await
+    }
+
+    /// Get a library playlist by ID with query options
+    pub async fn get_library_playlist_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Playlist> {
+        crate::utils::validate_resource_id(id)?;
+
+        let path = format!("v1/me/library/playlists/{}", id);
+        let response: ApiResponse<Playlist> = self
+            .http_client
+            .request(&path)
+            .query_params(options.to_params())
+            .get_json()
+            .await?;
+
+        response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppleMusicError::Api {
+                status: 404,
+                message: "Playlist not found".to_string(),
+            })
+    }
+
+    /// Get a playlist by ID with its `tracks` relationship filled completely
+    pub async fn get_playlist_with_tracks(&self, id: &str) -> Result<Playlist> {
+        self.get_playlist_with_tracks_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a playlist by ID with query options and its `tracks` relationship filled completely
+    ///
+    /// `tracks` is added to the relationships in `options.include`.
+    pub async fn get_playlist_with_tracks_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Playlist> {
         crate::utils::validate_resource_id(id)?;
 
-        let path = format!("v1/catalog/{}/albums/{}", self.config.storefront, id);
-        let response: ApiResponse<Album> = self.http_client.get_json(&path).await?;
+        let path = format!("v1/catalog/{}/playlists/{}", self.storefront(), id);
+        let mut params = self.catalog_params(options).await?;
+        add_include(&mut params, "tracks");
+        let response: ApiResponse<Playlist> = self
+            .http_client
+            .request(&path)
+            .query_params(params)
+            .get_json()
+            .await?;
+
+        let mut playlist =
+            response
+                .data
+                .into_iter()
+                .next()
+                .ok_or_else(|| AppleMusicError::Api {
+                    status: 404,
+                    message: "Playlist not found".to_string(),
+                })?;
+
+        let track_options = tracks_options(options);
+        let relationships = playlist.relationships.get_or_insert_with(Default::default);
+        let tracks = relationships.tracks.get_or_insert_with(Default::default);
+        if let Some(next) = tracks.next.take() {
+            let remaining: Vec<Song> = self
+                .get_all_pages(&next, self.catalog_params(&track_options).await?)
+                .await?;
+            tracks.data.extend(remaining);
+        } else if tracks.data.is_empty() {
+            // The relationship was not included, fetch it separately
+            tracks.data = self
+                .get_playlist_tracks_with_options(id, &track_options)
+                .await?;
+        }
+
+        Ok(playlist)
+    }
+
+    /// Get every track of a catalog playlist, following all pages
+    pub async fn get_playlist_tracks(&self, id: &str) -> Result<Vec<Song>> {
+        self.get_playlist_tracks_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every track of a catalog playlist, following all pages with query options
+    pub async fn get_playlist_tracks_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Song>> {
+        self.get_catalog_related("playlists", id, "tracks", options)
+            .await
+    }
+
+    /// Stream the tracks of a catalog playlist, following all pages
+    ///
+    /// Pages are requested as the stream is consumed, so very large
+    /// playlists don't have to be held in memory at once:
+    ///
+    /// ```rust,no_run
+    /// # use futures_util::TryStreamExt;
+    /// # async fn example(client: apple_music_api::AppleMusicClient) -> apple_music_api::error::Result<()> {
+    /// let mut tracks = client.stream_playlist_tracks("pl.f4d106fed2bd41149aaacabb233eb5eb");
+    /// while let Some(song) = tracks.try_next().await? {
+    ///     println!("{}", song.attributes.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_playlist_tracks<'a>(&'a self, id: &str) -> ResourceStream<'a, Song> {
+        self.stream_playlist_tracks_with_options(id, &QueryOptions::default())
+    }
+
+    /// Stream the tracks of a catalog playlist with query options, following all pages
+    pub fn stream_playlist_tracks_with_options<'a>(
+        &'a self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> ResourceStream<'a, Song> {
+        let id = id.to_string();
+        let options = options.clone();
+        stream::once(async move {
+            crate::utils::validate_resource_id(&id)?;
+            let path = format!("v1/catalog/{}/playlists/{}/tracks", self.storefront(), id);
+            Ok::<_, AppleMusicError>(self.stream_pages(path, self.catalog_params(&options).await?))
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Get multiple albums by IDs
+    pub async fn get_albums(&self, ids: &[&str]) -> Result<Vec<Album>> {
+        self.get_albums_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple albums by IDs with query options
+    pub async fn get_albums_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_resources("albums", ids, options).await
+    }
+
+    /// Get multiple artists by IDs
+    pub async fn get_artists(&self, ids: &[&str]) -> Result<Vec<Artist>> {
+        self.get_artists_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple artists by IDs with query options
+    pub async fn get_artists_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Artist>> {
+        self.get_catalog_resources("artists", ids, options).await
+    }
+
+    /// Get multiple songs by IDs
+    pub async fn get_songs(&self, ids: &[&str]) -> Result<Vec<Song>> {
+        self.get_songs_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple songs by IDs with query options
+    pub async fn get_songs_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Song>> {
+        self.get_catalog_resources("songs", ids, options).await
+    }
+
+    /// Get a music video by ID
+    pub async fn get_music_video(&self, id: &str) -> Result<MusicVideo> {
+        self.get_music_video_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a music video by ID with query options, e.g. to include relationships
+    pub async fn get_music_video_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<MusicVideo> {
+        self.get_catalog_resource("music-videos", id, "Music video not found", options)
+            .await
+    }
+
+    /// Get multiple music videos by IDs
+    pub async fn get_music_videos(&self, ids: &[&str]) -> Result<Vec<MusicVideo>> {
+        self.get_music_videos_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple music videos by IDs with query options
+    pub async fn get_music_videos_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<MusicVideo>> {
+        self.get_catalog_resources("music-videos", ids, options)
+            .await
+    }
+
+    /// Get the albums containing a music video
+    pub async fn get_music_video_albums(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_music_video_albums_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the albums containing a music video with query options
+    pub async fn get_music_video_albums_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("music-videos", id, "albums", options)
+            .await
+    }
+
+    /// Get the artists of a music video
+    pub async fn get_music_video_artists(&self, id: &str) -> Result<Vec<Artist>> {
+        self.get_music_video_artists_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the artists of a music video with query options
+    pub async fn get_music_video_artists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Artist>> {
+        self.get_catalog_related("music-videos", id, "artists", options)
+            .await
+    }
+
+    /// Get the genres of a music video
+    pub async fn get_music_video_genres(&self, id: &str) -> Result<Vec<Genre>> {
+        self.get_music_video_genres_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the genres of a music video with query options
+    pub async fn get_music_video_genres_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Genre>> {
+        self.get_catalog_related("music-videos", id, "genres", options)
+            .await
+    }
+
+    /// Get the songs matching a music video
+    pub async fn get_music_video_songs(&self, id: &str) -> Result<Vec<Song>> {
+        self.get_music_video_songs_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the songs matching a music video with query options
+    pub async fn get_music_video_songs_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Song>> {
+        self.get_catalog_related("music-videos", id, "songs", options)
+            .await
+    }
+
+    /// Get more music videos by the artist of a music video
+    pub async fn get_music_video_more_by_artist(&self, id: &str) -> Result<Vec<MusicVideo>> {
+        self.get_music_video_more_by_artist_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get more music videos by the artist of a music video with query options
+    pub async fn get_music_video_more_by_artist_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<MusicVideo>> {
+        self.get_catalog_related("music-videos", id, "view/more-by-artist", options)
+            .await
+    }
+
+    /// Get more music videos in the genre of a music video
+    pub async fn get_music_video_more_in_genre(&self, id: &str) -> Result<Vec<MusicVideo>> {
+        self.get_music_video_more_in_genre_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get more music videos in the genre of a music video with query options
+    pub async fn get_music_video_more_in_genre_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<MusicVideo>> {
+        self.get_catalog_related("music-videos", id, "view/more-in-genre", options)
+            .await
+    }
+
+    /// Get the songs with an ISRC
+    pub async fn get_songs_by_isrc(&self, isrc: &str) -> Result<Vec<Song>> {
+        self.get_songs_by_isrc_with_options(isrc, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the songs with an ISRC with query options
+    pub async fn get_songs_by_isrc_with_options(
+        &self,
+        isrc: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Song>> {
+        let mut matches = self
+            .get_songs_by_isrcs_with_options(&[isrc], options)
+            .await?;
+        Ok(matches.drain().flat_map(|(_, songs)| songs).collect())
+    }
+
+    /// Get the songs matching each of several ISRCs
+    ///
+    /// The map is keyed by normalized ISRC (see
+    /// [`normalize_isrc`](crate::utils::normalize_isrc)) and has an entry,
+    /// possibly empty, for every requested ISRC. Large batches are split
+    /// into requests of at most 25 ISRCs.
+    pub async fn get_songs_by_isrcs(&self, isrcs: &[&str]) -> Result<HashMap<String, Vec<Song>>> {
+        self.get_songs_by_isrcs_with_options(isrcs, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the songs matching each of several ISRCs with query options
+    pub async fn get_songs_by_isrcs_with_options(
+        &self,
+        isrcs: &[&str],
+        options: &QueryOptions,
+    ) -> Result<HashMap<String, Vec<Song>>> {
+        self.lookup_by_isrc("songs", isrcs, options, |song: &Song| {
+            song.attributes.isrc.as_deref()
+        })
+        .await
+    }
+
+    /// Get the music videos with an ISRC
+    pub async fn get_music_videos_by_isrc(&self, isrc: &str) -> Result<Vec<MusicVideo>> {
+        self.get_music_videos_by_isrc_with_options(isrc, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the music videos with an ISRC with query options
+    pub async fn get_music_videos_by_isrc_with_options(
+        &self,
+        isrc: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<MusicVideo>> {
+        let mut matches = self
+            .get_music_videos_by_isrcs_with_options(&[isrc], options)
+            .await?;
+        Ok(matches.drain().flat_map(|(_, videos)| videos).collect())
+    }
+
+    /// Get the music videos matching each of several ISRCs, as [`get_songs_by_isrcs`](Self::get_songs_by_isrcs) does
+    pub async fn get_music_videos_by_isrcs(
+        &self,
+        isrcs: &[&str],
+    ) -> Result<HashMap<String, Vec<MusicVideo>>> {
+        self.get_music_videos_by_isrcs_with_options(isrcs, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the music videos matching each of several ISRCs with query options
+    pub async fn get_music_videos_by_isrcs_with_options(
+        &self,
+        isrcs: &[&str],
+        options: &QueryOptions,
+    ) -> Result<HashMap<String, Vec<MusicVideo>>> {
+        self.lookup_by_isrc("music-videos", isrcs, options, |video: &MusicVideo| {
+            video.attributes.isrc.as_deref()
+        })
+        .await
+    }
+
+    /// Get the albums with a UPC or EAN barcode
+    pub async fn get_albums_by_upc(&self, upc: &str) -> Result<Vec<Album>> {
+        self.get_albums_by_upc_with_options(upc, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the albums with a UPC or EAN barcode with query options
+    pub async fn get_albums_by_upc_with_options(
+        &self,
+        upc: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        let mut matches = self
+            .get_albums_by_upcs_with_options(&[upc], options)
+            .await?;
+        Ok(matches.drain().flat_map(|(_, albums)| albums).collect())
+    }
+
+    /// Get the albums matching each of several UPC or EAN barcodes
+    ///
+    /// The map is keyed by the 13-digit EAN-13 form of each UPC, not the
+    /// spelling passed in, so `724384960650`, `0724384960650` and
+    /// `00724384960650` share the entry `0724384960650`. Use
+    /// [`normalize_upc`](crate::normalize_upc) to find the key of a UPC.
+    /// Every requested UPC has an entry, possibly empty.
+    pub async fn get_albums_by_upcs(&self, upcs: &[&str]) -> Result<HashMap<String, Vec<Album>>> {
+        self.get_albums_by_upcs_with_options(upcs, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the albums matching each of several UPC or EAN barcodes with query options
+    pub async fn get_albums_by_upcs_with_options(
+        &self,
+        upcs: &[&str],
+        options: &QueryOptions,
+    ) -> Result<HashMap<String, Vec<Album>>> {
+        self.lookup_by_filter(
+            "albums",
+            "upc",
+            upcs,
+            options,
+            crate::utils::normalize_upc,
+            upc_variants,
+            |album: &Album| album.attributes.upc.as_deref(),
+        )
+        .await
+    }
+
+    /// Get the songs equivalent to catalog song IDs in another storefront
+    ///
+    /// IDs belong to the client's storefront. Songs that can't be played in
+    /// `storefront` are listed in [`Equivalents::unmatched`].
+    pub async fn get_equivalent_songs(
+        &self,
+        ids: &[&str],
+        storefront: &str,
+    ) -> Result<Equivalents<Song>> {
+        self.get_equivalent_songs_with_options(ids, storefront, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the songs equivalent to catalog song IDs in another storefront with query options
+    ///
+    /// The options' language is checked against `storefront`. The client's
+    /// own language is not sent, since `storefront` may not support it.
+    pub async fn get_equivalent_songs_with_options(
+        &self,
+        ids: &[&str],
+        storefront: &str,
+        options: &QueryOptions,
+    ) -> Result<Equivalents<Song>> {
+        self.lookup_equivalents(
+            "songs",
+            ids,
+            storefront,
+            options,
+            |song: &Song| &song.id,
+            |song: &Song| {
+                let isrc = song.attributes.isrc.as_deref()?;
+                crate::utils::normalize_isrc(isrc).ok()
+            },
+        )
+        .await
+    }
+
+    /// Get the albums equivalent to catalog album IDs in another storefront
+    pub async fn get_equivalent_albums(
+        &self,
+        ids: &[&str],
+        storefront: &str,
+    ) -> Result<Equivalents<Album>> {
+        self.get_equivalent_albums_with_options(ids, storefront, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the albums equivalent to catalog album IDs in another storefront with query options
+    pub async fn get_equivalent_albums_with_options(
+        &self,
+        ids: &[&str],
+        storefront: &str,
+        options: &QueryOptions,
+    ) -> Result<Equivalents<Album>> {
+        self.lookup_equivalents(
+            "albums",
+            ids,
+            storefront,
+            options,
+            |album: &Album| &album.id,
+            |album: &Album| {
+                let upc = album.attributes.upc.as_deref()?;
+                crate::utils::normalize_upc(upc).ok()
+            },
+        )
+        .await
+    }
+
+    /// Get the music videos equivalent to catalog music video IDs in another storefront
+    pub async fn get_equivalent_music_videos(
+        &self,
+        ids: &[&str],
+        storefront: &str,
+    ) -> Result<Equivalents<MusicVideo>> {
+        self.get_equivalent_music_videos_with_options(ids, storefront, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the music videos equivalent to catalog music video IDs in another storefront with query options
+    pub async fn get_equivalent_music_videos_with_options(
+        &self,
+        ids: &[&str],
+        storefront: &str,
+        options: &QueryOptions,
+    ) -> Result<Equivalents<MusicVideo>> {
+        self.lookup_equivalents(
+            "music-videos",
+            ids,
+            storefront,
+            options,
+            |video: &MusicVideo| &video.id,
+            |video: &MusicVideo| {
+                let isrc = video.attributes.isrc.as_deref()?;
+                crate::utils::normalize_isrc(isrc).ok()
+            },
+        )
+        .await
+    }
+
+    /// Look up a catalog collection of another storefront with `filter[equivalents]`
+    ///
+    /// IDs are sent in batches of up to [`MAX_FILTER_VALUES`]. The response
+    /// doesn't say which requested ID a resource stands for, so resources
+    /// returned under new IDs are matched by `key_of`, the normalized ISRC or
+    /// UPC, to the requested resources, which are fetched from the client's
+    /// storefront once per batch.
+    async fn lookup_equivalents<T, I, K>(
+        &self,
+        collection: &str,
+        ids: &[&str],
+        storefront: &str,
+        options: &QueryOptions,
+        id_of: I,
+        key_of: K,
+    ) -> Result<Equivalents<T>>
+    where
+        T: DeserializeOwned + Clone,
+        I: Fn(&T) -> &str,
+        K: Fn(&T) -> Option<String>,
+    {
+        let storefront = self.config.check_storefront(storefront)?;
+
+        let mut requested: Vec<&str> = Vec::new();
+        for id in ids {
+            crate::utils::validate_resource_id(id)?;
+            if !requested.contains(id) {
+                requested.push(id);
+            }
+        }
+
+        let path = format!("v1/catalog/{}/{}", storefront, collection);
+        let mut params = match &options.language {
+            Some(language) => vec![(
+                "l".to_string(),
+                self.check_language(&storefront, language).await?,
+            )],
+            None => Vec::new(),
+        };
+        params.extend(options.to_params());
+
+        let mut matches: HashMap<String, T> = HashMap::new();
+        for batch in requested.chunks(MAX_FILTER_VALUES) {
+            let mut batch_params = vec![("filter[equivalents]".to_string(), batch.join(","))];
+            batch_params.extend(params.iter().cloned());
+            let items: Vec<T> = self.get_all_pages(&path, batch_params).await?;
+
+            if let [id] = batch {
+                if let Some(item) = items.into_iter().next() {
+                    matches.insert(id.to_string(), item);
+                }
+                continue;
+            }
+
+            let mut renamed = Vec::new();
+            for item in items {
+                let id = id_of(&item).to_string();
+                if batch.contains(&id.as_str()) && !matches.contains_key(&id) {
+                    matches.insert(id, item);
+                } else {
+                    renamed.push(item);
+                }
+            }
+            if renamed.is_empty() {
+                continue;
+            }
+
+            let unresolved: Vec<&str> = batch
+                .iter()
+                .copied()
+                .filter(|id| !matches.contains_key(*id))
+                .collect();
+            let sources: Vec<T> = self
+                .get_catalog_resources(collection, &unresolved, &QueryOptions::default())
+                .await?;
+            for item in renamed {
+                let Some(key) = key_of(&item) else {
+                    continue;
+                };
+                for source in &sources {
+                    let id = id_of(source);
+                    if key_of(source).as_ref() == Some(&key)
+                        && unresolved.contains(&id)
+                        && !matches.contains_key(id)
+                    {
+                        matches.insert(id.to_string(), item.clone());
+                    }
+                }
+            }
+        }
+
+        let unmatched = requested
+            .iter()
+            .filter(|id| !matches.contains_key(**id))
+            .map(|id| id.to_string())
+            .collect();
+
+        Ok(Equivalents {
+            storefront,
+            matches,
+            unmatched,
+        })
+    }
+
+    /// Look up a catalog collection with `filter[isrc]` and group the matches
+    async fn lookup_by_isrc<T, F>(
+        &self,
+        collection: &str,
+        isrcs: &[&str],
+        options: &QueryOptions,
+        isrc_of: F,
+    ) -> Result<HashMap<String, Vec<T>>>
+    where
+        T: DeserializeOwned,
+        F: Fn(&T) -> Option<&str>,
+    {
+        self.lookup_by_filter(
+            collection,
+            "isrc",
+            isrcs,
+            options,
+            crate::utils::normalize_isrc,
+            |isrc| vec![isrc.to_string()],
+            isrc_of,
+        )
+        .await
+    }
+
+    /// Look up a catalog collection with `filter[<filter>]` and group the matches
+    ///
+    /// Codes are grouped by their normalized form. `variants` lists the
+    /// spellings sent to the API for a normalized code, and requests are
+    /// split so none carries more than [`MAX_FILTER_VALUES`] values.
+    #[allow(clippy::too_many_arguments)]
+    async fn lookup_by_filter<T, N, V, K>(
+        &self,
+        collection: &str,
+        filter: &str,
+        codes: &[&str],
+        options: &QueryOptions,
+        normalize: N,
+        variants: V,
+        key_of: K,
+    ) -> Result<HashMap<String, Vec<T>>>
+    where
+        T: DeserializeOwned,
+        N: Fn(&str) -> Result<String>,
+        V: Fn(&str) -> Vec<String>,
+        K: Fn(&T) -> Option<&str>,
+    {
+        let mut normalized = Vec::new();
+        for code in codes {
+            let code = normalize(code)?;
+            if !normalized.contains(&code) {
+                normalized.push(code);
+            }
+        }
+
+        let mut matches: HashMap<String, Vec<T>> = normalized
+            .iter()
+            .map(|code| (code.clone(), Vec::new()))
+            .collect();
+
+        let mut batches: Vec<Vec<String>> = Vec::new();
+        for code in &normalized {
+            let values = variants(code);
+            match batches.last_mut() {
+                Some(batch) if batch.len() + values.len() <= MAX_FILTER_VALUES => {
+                    batch.extend(values)
+                }
+                _ => batches.push(values),
+            }
+        }
+
+        let path = format!("v1/catalog/{}/{}", self.storefront(), collection);
+        for batch in batches {
+            let mut params = vec![(format!("filter[{}]", filter), batch.join(","))];
+            params.extend(self.catalog_params(options).await?);
+
+            let items: Vec<T> = self.get_all_pages(&path, params).await?;
+            for item in items {
+                let code = key_of(&item).and_then(|code| normalize(code).ok());
+                if let Some(entry) = code.and_then(|code| matches.get_mut(&code)) {
+                    entry.push(item);
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Get the default charts for the given media types
+    pub async fn get_charts(&self, types: &[MediaType]) -> Result<ChartsResponse> {
+        self.get_charts_with_options(&ChartOptions::new(types.to_vec()))
+            .await
+    }
+
+    /// Get charts with additional options
+    ///
+    /// Charts exist for songs, albums, playlists and music videos. Use
+    /// [`get_full_chart`](Self::get_full_chart) to follow a chart's pages.
+    pub async fn get_charts_with_options(&self, options: &ChartOptions) -> Result<ChartsResponse> {
+        if options.types.is_empty() {
+            return Err(AppleMusicError::invalid_request(
+                "At least one chart type is required",
+            ));
+        }
+        if let Some(media_type) = options.types.iter().find(|media_type| {
+            !matches!(
+                media_type,
+                MediaType::Songs
+                    | MediaType::Albums
+                    | MediaType::Playlists
+                    | MediaType::MusicVideos
+            )
+        }) {
+            return Err(AppleMusicError::invalid_request(format!(
+                "There are no charts for {}",
+                media_type
+            )));
+        }
+
+        let mut params = vec![(
+            "types".to_string(),
+            crate::utils::media_types_to_string(&options.types),
+        )];
+        if let Some(chart) = &options.chart {
+            params.push(("chart".to_string(), chart.clone()));
+        }
+        if let Some(genre) = &options.genre {
+            crate::utils::validate_resource_id(genre)?;
+            params.push(("genre".to_string(), genre.clone()));
+        }
+        if let Some(limit) = options.limit {
+            params.push(("limit".to_string(), limit.to_string()));
+        }
+        if let Some(offset) = options.offset {
+            params.push(("offset".to_string(), offset.to_string()));
+        }
+        params.extend(self.language_params(options.language.as_deref()).await?);
+
+        let path = format!("v1/catalog/{}/charts", self.storefront());
+        self.http_client
+            .request(&path)
+            .query_params(params)
+            .get_json()
+            .await
+    }
+
+    /// Get the next page of a chart, if there is one
+    pub async fn get_chart_next_page<T: ChartItem>(
+        &self,
+        chart: &Chart<T>,
+    ) -> Result<Option<Chart<T>>> {
+        let Some(next) = &chart.next else {
+            return Ok(None);
+        };
+
+        let mut response: ChartsResponse = self
+            .http_client
+            .request(next)
+            .query_params(self.language_params(None).await?)
+            .get_json()
+            .await?;
+
+        Ok(T::take_charts(&mut response.results)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|page| page.chart == chart.chart))
+    }
+
+    /// Follow every page of a chart, appending the entries to it
+    pub async fn get_full_chart<T: ChartItem>(&self, mut chart: Chart<T>) -> Result<Chart<T>> {
+        while let Some(page) = self.get_chart_next_page(&chart).await? {
+            chart.data.extend(page.data);
+            chart.next = page.next;
+        }
+        chart.next = None;
+        Ok(chart)
+    }
+
+    /// Get every genre of the storefront, following all pages
+    pub async fn get_storefront_genres(&self) -> Result<Vec<Genre>> {
+        self.get_storefront_genres_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get every genre of the storefront with query options, following all pages
+    pub async fn get_storefront_genres_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<Vec<Genre>> {
+        let path = format!("v1/catalog/{}/genres", self.storefront());
+        self.get_all_pages(&path, self.catalog_params(options).await?)
+            .await
+    }
+
+    /// Get a genre by ID
+    pub async fn get_genre(&self, id: &str) -> Result<Genre> {
+        self.get_genre_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a genre by ID with query options, e.g. to include relationships
+    pub async fn get_genre_with_options(&self, id: &str, options: &QueryOptions) -> Result<Genre> {
+        self.get_catalog_resource("genres", id, "Genre not found", options)
+            .await
+    }
+
+    /// Get multiple genres by IDs
+    pub async fn get_genres(&self, ids: &[&str]) -> Result<Vec<Genre>> {
+        self.get_genres_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple genres by IDs with query options
+    pub async fn get_genres_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Genre>> {
+        self.get_catalog_resources("genres", ids, options).await
+    }
+
+    /// Get the charts of a genre for the given media types
+    pub async fn get_genre_charts(&self, id: &str, types: &[MediaType]) -> Result<ChartsResponse> {
+        self.get_charts_with_options(&ChartOptions::new(types.to_vec()).with_genre(id))
+            .await
+    }
+
+    /// Get every station of a genre
+    pub async fn get_genre_stations(&self, id: &str) -> Result<Vec<Station>> {
+        self.get_genre_stations_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every station of a genre with query options
+    pub async fn get_genre_stations_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Station>> {
+        self.get_catalog_related("genres", id, "stations", options)
+            .await
+    }
+
+    /// Get a station by ID
+    pub async fn get_station(&self, id: &str) -> Result<Station> {
+        self.get_station_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a station by ID with query options, e.g. to include relationships
+    pub async fn get_station_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Station> {
+        self.get_catalog_resource("stations", id, "Station not found", options)
+            .await
+    }
+
+    /// Get multiple stations by IDs
+    pub async fn get_stations(&self, ids: &[&str]) -> Result<Vec<Station>> {
+        self.get_stations_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple stations by IDs with query options
+    pub async fn get_stations_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Station>> {
+        self.get_catalog_resources("stations", ids, options).await
+    }
+
+    /// Get the Apple Music live radio stations
+    pub async fn get_live_radio_stations(&self) -> Result<Vec<Station>> {
+        self.get_live_radio_stations_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get the Apple Music live radio stations with query options
+    pub async fn get_live_radio_stations_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<Vec<Station>> {
+        let path = format!("v1/catalog/{}/stations", self.storefront());
+        let mut params = vec![(
+            "filter[featured]".to_string(),
+            "apple-music-live-radio".to_string(),
+        )];
+        params.extend(self.catalog_params(options).await?);
+        self.get_all_pages(&path, params).await
+    }
+
+    /// Get every station genre of the storefront, following all pages
+    pub async fn get_storefront_station_genres(&self) -> Result<Vec<StationGenre>> {
+        self.get_storefront_station_genres_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get every station genre of the storefront with query options, following all pages
+    pub async fn get_storefront_station_genres_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<Vec<StationGenre>> {
+        let path = format!("v1/catalog/{}/station-genres", self.storefront());
+        self.get_all_pages(&path, self.catalog_params(options).await?)
+            .await
+    }
+
+    /// Get a station genre by ID
+    pub async fn get_station_genre(&self, id: &str) -> Result<StationGenre> {
+        self.get_station_genre_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a station genre by ID with query options, e.g. to include relationships
+    pub async fn get_station_genre_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<StationGenre> {
+        self.get_catalog_resource("station-genres", id, "Station genre not found", options)
+            .await
+    }
+
+    /// Get multiple station genres by IDs
+    pub async fn get_station_genres(&self, ids: &[&str]) -> Result<Vec<StationGenre>> {
+        self.get_station_genres_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple station genres by IDs with query options
+    pub async fn get_station_genres_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<StationGenre>> {
+        self.get_catalog_resources("station-genres", ids, options)
+            .await
+    }
+
+    /// Get every station of a station genre
+    pub async fn get_station_genre_stations(&self, id: &str) -> Result<Vec<Station>> {
+        self.get_station_genre_stations_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every station of a station genre with query options
+    pub async fn get_station_genre_stations_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Station>> {
+        self.get_catalog_related("station-genres", id, "stations", options)
+            .await
+    }
+
+    /// Get a curator by ID
+    pub async fn get_curator(&self, id: &str) -> Result<Curator> {
+        self.get_curator_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a curator by ID with query options, e.g. to include relationships
+    pub async fn get_curator_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Curator> {
+        self.get_catalog_resource("curators", id, "Curator not found", options)
+            .await
+    }
+
+    /// Get multiple curators by IDs
+    pub async fn get_curators(&self, ids: &[&str]) -> Result<Vec<Curator>> {
+        self.get_curators_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple curators by IDs with query options
+    pub async fn get_curators_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Curator>> {
+        self.get_catalog_resources("curators", ids, options).await
+    }
+
+    /// Get every playlist of a curator
+    pub async fn get_curator_playlists(&self, id: &str) -> Result<Vec<Playlist>> {
+        self.get_curator_playlists_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every playlist of a curator with query options
+    pub async fn get_curator_playlists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Playlist>> {
+        self.get_catalog_related("curators", id, "playlists", options)
+            .await
+    }
+
+    /// Get an Apple curator by ID
+    pub async fn get_apple_curator(&self, id: &str) -> Result<Curator> {
+        self.get_apple_curator_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get an Apple curator by ID with query options, e.g. to include relationships
+    pub async fn get_apple_curator_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Curator> {
+        self.get_catalog_resource("apple-curators", id, "Curator not found", options)
+            .await
+    }
+
+    /// Get multiple Apple curators by IDs
+    pub async fn get_apple_curators(&self, ids: &[&str]) -> Result<Vec<Curator>> {
+        self.get_apple_curators_with_options(ids, &QueryOptions::default())
+            .await
+    }
+
+    /// Get multiple Apple curators by IDs with query options
+    pub async fn get_apple_curators_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Curator>> {
+        self.get_catalog_resources("apple-curators", ids, options)
+            .await
+    }
+
+    /// Get every playlist of an Apple curator
+    pub async fn get_apple_curator_playlists(&self, id: &str) -> Result<Vec<Playlist>> {
+        self.get_apple_curator_playlists_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get every playlist of an Apple curator with query options
+    pub async fn get_apple_curator_playlists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Playlist>> {
+        self.get_catalog_related("apple-curators", id, "playlists", options)
+            .await
+    }
+
+    /// Get the curator of a catalog playlist
+    ///
+    /// The curator's `resource_type` is `curators` or `apple-curators`.
+    pub async fn get_playlist_curator(&self, playlist_id: &str) -> Result<Curator> {
+        self.get_playlist_curator_with_options(playlist_id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get the curator of a catalog playlist with query options
+    pub async fn get_playlist_curator_with_options(
+        &self,
+        playlist_id: &str,
+        options: &QueryOptions,
+    ) -> Result<Curator> {
+        crate::utils::validate_resource_id(playlist_id)?;
+
+        let path = format!(
+            "v1/catalog/{}/playlists/{}/curator",
+            self.storefront(),
+            playlist_id
+        );
+        let response: ApiResponse<Curator> = self
+            .http_client
+            .request(&path)
+            .query_params(self.catalog_params(options).await?)
+            .get_json()
+            .await?;
 
         response
             .data
@@ -162,110 +1827,211 @@ impl AppleMusicClient {
             .next()
             .ok_or_else(|| AppleMusicError::Api {
                 status: 404,
-                message: "Album not found".to_string(),
+                message: "Curator not found".to_string(),
             })
     }
 
-    /// Get an artist by ID
-    pub async fn get_artist(&self, id: &str) -> Result<Artist> {
-        crate::utils::validate_resource_id(id)?;
+    /// Get an activity by ID
+    pub async fn get_activity(&self, id: &str) -> Result<Activity> {
+        self.get_activity_with_options(id, &QueryOptions::default())
+            .await
+    }
 
-        let path = format!("v1/catalog/{}/artists/{}", self.config.storefront, id);
-        let response: ApiResponse<Artist> = self.http_client.get_json(&path).await?;
+    /// Get an activity by ID with query options, e.g. to include relationships
+    pub async fn get_activity_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Activity> {
+        self.get_catalog_resource("activities", id, "Activity not found", options)
+            .await
+    }
 
-        response
-            .data
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppleMusicError::Api {
-                status: 404,
-                message: "Artist not found".to_string(),
-            })
+    /// Get multiple activities by IDs
+    pub async fn get_activities(&self, ids: &[&str]) -> Result<Vec<Activity>> {
+        self.get_activities_with_options(ids, &QueryOptions::default())
+            .await
     }
 
-    /// Get a song by ID
-    pub async fn get_song(&self, id: &str) -> Result<Song> {
-        crate::utils::validate_resource_id(id)?;
+    /// Get multiple activities by IDs with query options
+    pub async fn get_activities_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<Activity>> {
+        self.get_catalog_resources("activities", ids, options).await
+    }
 
-        let path = format!("v1/catalog/{}/songs/{}", self.config.storefront, id);
-        let response: ApiResponse<Song> = self.http_client.get_json(&path).await?;
+    /// Get every playlist of an activity
+    pub async fn get_activity_playlists(&self, id: &str) -> Result<Vec<Playlist>> {
+        self.get_activity_playlists_with_options(id, &QueryOptions::default())
+            .await
+    }
 
-        response
-            .data
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppleMusicError::Api {
-                status: 404,
-                message: "Song not found".to_string(),
-            })
+    /// Get every playlist of an activity with query options
+    pub async fn get_activity_playlists_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Playlist>> {
+        self.get_catalog_related("activities", id, "playlists", options)
+            .await
     }
 
-    /// Get a playlist by ID
-    pub async fn get_catalog_playlist(&self, id: &str) -> Result<Playlist> {
-        crate::utils::validate_resource_id(id)?;
+    /// Get a record label by ID
+    pub async fn get_record_label(&self, id: &str) -> Result<RecordLabel> {
+        self.get_record_label_with_options(id, &QueryOptions::default())
+            .await
+    }
 
-        let path = format!("v1/catalog/{}/playlists/{}", self.config.storefront, id);
-        let response: ApiResponse<Playlist> = self.http_client.get_json(&path).await?;
+    /// Get a record label by ID with query options, e.g. to include relationships
+    pub async fn get_record_label_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<RecordLabel> {
+        self.get_catalog_resource("record-labels", id, "Record label not found", options)
+            .await
+    }
 
-        response
-            .data
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppleMusicError::Api {
-                status: 404,
-                message: "Playlist not found".to_string(),
-            })
+    /// Get multiple record labels by IDs
+    pub async fn get_record_labels(&self, ids: &[&str]) -> Result<Vec<RecordLabel>> {
+        self.get_record_labels_with_options(ids, &QueryOptions::default())
+            .await
     }
 
-    /// Get a playlist by ID
-    pub async fn get_library_playlist(&self, id: &str) -> Result<Playlist> {
-        crate::utils::validate_resource_id(id)?;
+    /// Get multiple record labels by IDs with query options
+    pub async fn get_record_labels_with_options(
+        &self,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<RecordLabel>> {
+        self.get_catalog_resources("record-labels", ids, options)
+            .await
+    }
 
-        let path = format!("v1/me/library/playlists/{}", id);
-        let response: ApiResponse<Playlist> = self.http_client.get_json(&path).await?;
+    /// Get a record label's latest releases
+    pub async fn get_record_label_latest_releases(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_record_label_latest_releases_with_options(id, &QueryOptions::default())
+            .await
+    }
 
-        response
-            .data
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppleMusicError::Api {
-                status: 404,
-                message: "Playlist not found".to_string(),
-            })
+    /// Get a record label's latest releases with query options
+    pub async fn get_record_label_latest_releases_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("record-labels", id, "view/latest-releases", options)
+            .await
     }
 
-    /// Get a playlist by ID
-    pub async fn get_library_playlist_with_tracks(&self, id: &str) -> Result<Playlist> {
-        crate::utils::validate_resource_id(id)?;
+    /// Get a record label's top releases
+    pub async fn get_record_label_top_releases(&self, id: &str) -> Result<Vec<Album>> {
+        self.get_record_label_top_releases_with_options(id, &QueryOptions::default())
+            .await
+    }
+
+    /// Get a record label's top releases with query options
+    pub async fn get_record_label_top_releases_with_options(
+        &self,
+        id: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<Album>> {
+        self.get_catalog_related("record-labels", id, "view/top-releases", options)
+            .await
+    }
 
-        let path = format!("v1/me/library/playlists/{}?include=tracks", id);
+    /// Get catalog resources of several types in a single request
+    ///
+    /// Uses `ids[type]` query parameters, for example
+    /// `?ids[songs]=1,2&ids[albums]=3`, and groups the results by type. IDs
+    /// that were requested but not returned are listed in
+    /// [`CatalogResources::missing`].
+    pub async fn get_catalog_resources_by_type(
+        &self,
+        ids: &[(MediaType, &[&str])],
+    ) -> Result<CatalogResources> {
+        self.get_catalog_resources_by_type_with_options(ids, &QueryOptions::default())
+            .await
+    }
 
-        let response: ApiResponse<Playlist> = self.http_client.get_json(&path).await?;
+    /// Get catalog resources of several types in a single request with query options
+    pub async fn get_catalog_resources_by_type_with_options(
+        &self,
+        ids: &[(MediaType, &[&str])],
+        options: &QueryOptions,
+    ) -> Result<CatalogResources> {
+        let mut requested: Vec<(MediaType, Vec<&str>)> = Vec::new();
+        for (media_type, type_ids) in ids {
+            for id in *type_ids {
+                crate::utils::validate_resource_id(id)?;
+            }
+
+            let index = match requested.iter().position(|(t, _)| t == media_type) {
+                Some(index) => index,
+                None => {
+                    requested.push((*media_type, Vec::new()));
+                    requested.len() - 1
+                }
+            };
+            for id in *type_ids {
+                if !requested[index].1.contains(id) {
+                    requested[index].1.push(id);
+                }
+            }
+        }
+        requested.retain(|(_, type_ids)| !type_ids.is_empty());
 
-        response
-            .data
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppleMusicError::Api {
-                status: 404,
-                message: "Playlist not found".to_string(),
+        let mut resources = CatalogResources::default();
+        if requested.is_empty() {
+            return Ok(resources);
+        }
+
+        let path = format!("v1/catalog/{}", self.storefront());
+        let mut params: Vec<(String, String)> = requested
+            .iter()
+            .map(|(media_type, type_ids)| {
+                (format!("ids[{}]", media_type.as_str()), type_ids.join(","))
             })
+            .collect();
+        params.extend(self.catalog_params(options).await?);
+
+        let data: Vec<serde_json::Value> = self.get_all_pages(&path, params).await?;
+        for resource in data {
+            resources.insert(resource)?;
+        }
+
+        for (media_type, type_ids) in &requested {
+            let found = resources.ids(*media_type);
+            let missing: Vec<String> = type_ids
+                .iter()
+                .filter(|id| !found.contains(id))
+                .map(|id| id.to_string())
+                .collect();
+            if !missing.is_empty() {
+                resources.missing.insert(*media_type, missing);
+            }
+        }
+
+        Ok(resources)
     }
 
-    /// Get a playlist by ID with its tracks included
-    pub async fn get_playlist_with_tracks(&self, id: &str) -> Result<Playlist> {
+    /// Get a catalog resource by ID
+    async fn get_catalog_resource<T: DeserializeOwned>(
+        &self,
+        collection: &str,
+        id: &str,
+        not_found: &str,
+        options: &QueryOptions,
+    ) -> Result<T> {
         crate::utils::validate_resource_id(id)?;
 
-        let path = format!("v1/catalog/{}/playlists/{}", self.config.storefront, id);
-        let params = vec![("include", "tracks".to_string())];
-        let params: Vec<(String, String)> = params
-            .into_iter()
-            .map(|(k, v)| (k.to_string(), v))
-            .collect();
-        let response: ApiResponse<Playlist> = self
+        let path = format!("v1/catalog/{}/{}/{}", self.storefront(), collection, id);
+        let response: ApiResponse<T> = self
             .http_client
             .request(&path)
-            .query_params(params)
+            .query_params(self.catalog_params(options).await?)
             .get_json()
             .await?;
 
@@ -275,32 +2041,17 @@ impl AppleMusicClient {
             .next()
             .ok_or_else(|| AppleMusicError::Api {
                 status: 404,
-                message: "Playlist not found".to_string(),
+                message: not_found.to_string(),
             })
     }
 
-    /// Get multiple albums by IDs
-    pub async fn get_albums(&self, ids: &[&str]) -> Result<Vec<Album>> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        for id in ids {
-            crate::utils::validate_resource_id(id)?;
-        }
-
-        let ids_param = ids.join(",");
-        let path = format!(
-            "v1/catalog/{}/albums?ids={}",
-            self.config.storefront, ids_param
-        );
-        let response: ApiResponse<Album> = self.http_client.get_json(&path).await?;
-
-        Ok(response.data)
-    }
-
-    /// Get multiple artists by IDs
-    pub async fn get_artists(&self, ids: &[&str]) -> Result<Vec<Artist>> {
+    /// Get multiple catalog resources by IDs
+    async fn get_catalog_resources<T: DeserializeOwned>(
+        &self,
+        collection: &str,
+        ids: &[&str],
+        options: &QueryOptions,
+    ) -> Result<Vec<T>> {
         if ids.is_empty() {
             return Ok(Vec::new());
         }
@@ -309,34 +2060,31 @@ impl AppleMusicClient {
             crate::utils::validate_resource_id(id)?;
         }
 
-        let ids_param = ids.join(",");
-        let path = format!(
-            "v1/catalog/{}/artists?ids={}",
-            self.config.storefront, ids_param
-        );
-        let response: ApiResponse<Artist> = self.http_client.get_json(&path).await?;
-
-        Ok(response.data)
+        let path = format!("v1/catalog/{}/{}", self.storefront(), collection);
+        let mut params = vec![("ids".to_string(), ids.join(","))];
+        params.extend(self.catalog_params(options).await?);
+        self.get_all_pages(&path, params).await
     }
 
-    /// Get multiple songs by IDs
-    pub async fn get_songs(&self, ids: &[&str]) -> Result<Vec<Song>> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        for id in ids {
-            crate::utils::validate_resource_id(id)?;
-        }
+    /// Fetch every page of a relationship or view of a catalog resource
+    async fn get_catalog_related<T: DeserializeOwned>(
+        &self,
+        collection: &str,
+        id: &str,
+        related: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<T>> {
+        crate::utils::validate_resource_id(id)?;
 
-        let ids_param = ids.join(",");
         let path = format!(
-            "v1/catalog/{}/songs?ids={}",
-            self.config.storefront, ids_param
+            "v1/catalog/{}/{}/{}/{}",
+            self.storefront(),
+            collection,
+            id,
+            related
         );
-        let response: ApiResponse<Song> = self.http_client.get_json(&path).await?;
-
-        Ok(response.data)
+        self.get_all_pages(&path, self.catalog_params(options).await?)
+            .await
     }
 
     // ===== LIBRARY API METHODS =====
@@ -344,40 +2092,92 @@ impl AppleMusicClient {
 
     /// Get the user's library albums
     pub async fn get_library_albums(&self) -> Result<LibraryAlbumsResponse> {
+        self.get_library_albums_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get the user's library albums with query options
+    pub async fn get_library_albums_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<LibraryAlbumsResponse> {
         self.check_user_token()?;
 
-        let response: LibraryAlbumsResponse =
-            self.http_client.get_json("v1/me/library/albums").await?;
+        let response: LibraryAlbumsResponse = self
+            .http_client
+            .request("v1/me/library/albums")
+            .query_params(options.to_params())
+            .get_json()
+            .await?;
 
         Ok(response)
     }
 
     /// Get the user's library artists
     pub async fn get_library_artists(&self) -> Result<LibraryArtistsResponse> {
+        self.get_library_artists_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get the user's library artists with query options
+    pub async fn get_library_artists_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<LibraryArtistsResponse> {
         self.check_user_token()?;
 
-        let response: LibraryArtistsResponse =
-            self.http_client.get_json("v1/me/library/artists").await?;
+        let response: LibraryArtistsResponse = self
+            .http_client
+            .request("v1/me/library/artists")
+            .query_params(options.to_params())
+            .get_json()
+            .await?;
 
         Ok(response)
     }
 
     /// Get the user's library songs
     pub async fn get_library_songs(&self) -> Result<LibrarySongsResponse> {
+        self.get_library_songs_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get the user's library songs with query options
+    pub async fn get_library_songs_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<LibrarySongsResponse> {
         self.check_user_token()?;
 
-        let response: LibrarySongsResponse =
-            self.http_client.get_json("v1/me/library/songs").await?;
+        let response: LibrarySongsResponse = self
+            .http_client
+            .request("v1/me/library/songs")
+            .query_params(options.to_params())
+            .get_json()
+            .await?;
 
         Ok(response)
     }
 
     /// Get the user's library playlists
     pub async fn get_library_playlists(&self) -> Result<LibraryPlaylistsResponse> {
+        self.get_library_playlists_with_options(&QueryOptions::default())
+            .await
+    }
+
+    /// Get the user's library playlists with query options
+    pub async fn get_library_playlists_with_options(
+        &self,
+        options: &QueryOptions,
+    ) -> Result<LibraryPlaylistsResponse> {
         self.check_user_token()?;
 
-        let response: LibraryPlaylistsResponse =
-            self.http_client.get_json("v1/me/library/playlists").await?;
+        let response: LibraryPlaylistsResponse = self
+            .http_client
+            .request("v1/me/library/playlists")
+            .query_params(options.to_params())
+            .get_json()
+            .await?;
 
         Ok(response)
     }
@@ -447,9 +2247,17 @@ impl AppleMusicClient {
 
     // ===== UTILITY METHODS =====
 
-    /// Get the current storefront
-    pub fn storefront(&self) -> &str {
-        &self.config.storefront
+    /// Get the storefront used for catalog requests
+    pub fn storefront(&self) -> String {
+        self.storefront
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Switch catalog requests to another storefront
+    fn set_storefront(&self, storefront: String) {
+        *self.storefront.write().unwrap_or_else(|e| e.into_inner()) = storefront;
     }
 
     /// Get the base URL
@@ -457,6 +2265,98 @@ impl AppleMusicClient {
         &self.config.base_url
     }
 
+    /// Get the language tag sent with catalog requests
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Get a client that requests catalog metadata in another language
+    ///
+    /// The language must be one of the storefront's
+    /// [`supported_language_tags`](StorefrontAttributes::supported_language_tags).
+    /// The returned client shares its connection and tokens with this one.
+    /// To change the language of a single request instead, set it on the
+    /// request's options, e.g. with [`QueryOptions::with_language`] and
+    /// [`get_album_with_options`](Self::get_album_with_options).
+    ///
+    /// ```rust,no_run
+    /// # async fn example(client: apple_music_api::AppleMusicClient) -> apple_music_api::error::Result<()> {
+    /// let album = client.with_language("fr-CA").await?.get_album("1440857781").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_language(&self, language: &str) -> Result<Self> {
+        let language = self.check_language(&self.storefront(), language).await?;
+        let mut client = self.clone();
+        client.language = Some(language);
+        Ok(client)
+    }
+
+    /// Check a language tag against a storefront's supported languages
+    ///
+    /// Returns the tag as spelled by the storefront. The supported languages
+    /// are fetched once per storefront.
+    async fn check_language(&self, storefront: &str, language: &str) -> Result<String> {
+        let language = crate::utils::parse_language_tag(language)?;
+
+        let cached = self
+            .supported_languages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(storefront)
+            .cloned();
+        let supported = match cached {
+            Some(supported) => supported,
+            None => {
+                let supported = self
+                    .fetch_storefront(storefront)
+                    .await?
+                    .attributes
+                    .map(|attributes| attributes.supported_language_tags)
+                    .unwrap_or_default();
+                self.supported_languages
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(storefront.to_string(), supported.clone());
+                supported
+            }
+        };
+
+        supported
+            .iter()
+            .find(|tag| tag.eq_ignore_ascii_case(&language))
+            .cloned()
+            .ok_or_else(|| {
+                AppleMusicError::invalid_request(format!(
+                    "Language {:?} is not supported by storefront {:?} (supported: {})",
+                    language,
+                    storefront,
+                    supported.join(", ")
+                ))
+            })
+    }
+
+    /// Query parameters for a catalog request with query options
+    ///
+    /// The `l` parameter for the options' language, or the client's, followed
+    /// by the other options.
+    async fn catalog_params(&self, options: &QueryOptions) -> Result<Vec<(String, String)>> {
+        let mut params = self.language_params(options.language.as_deref()).await?;
+        params.extend(options.to_params());
+        Ok(params)
+    }
+
+    /// The `l` parameter for `language`, or the client's language
+    async fn language_params(&self, language: Option<&str>) -> Result<Vec<(String, String)>> {
+        match language.or(self.language.as_deref()) {
+            Some(language) => Ok(vec![(
+                "l".to_string(),
+                self.check_language(&self.storefront(), language).await?,
+            )]),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Check if user token is required but not available
     fn check_user_token(&self) -> Result<()> {
         if !self.http_client.has_user_token() {
@@ -474,10 +2374,12 @@ impl AppleMusicClient {
             .into_iter()
             .map(|(k, v)| (k.to_string(), v))
             .collect();
+        let path = format!("v1/catalog/{}/search/hints", self.storefront());
         let response: SearchHintsResponse = self
             .http_client
-            .request("v1/catalog/{storefront}/search/hints")
+            .request(&path)
             .query_params(params)
+            .query_params(self.language_params(None).await?)
             .get_json()
             .await?;
 
@@ -491,10 +2393,12 @@ impl AppleMusicClient {
             .into_iter()
             .map(|(k, v)| (k.to_string(), v))
             .collect();
+        let path = format!("v1/catalog/{}/search/suggestions", self.storefront());
         let response: SearchSuggestionsResponse = self
             .http_client
-            .request("v1/catalog/{storefront}/search/suggestions")
+            .request(&path)
             .query_params(params)
+            .query_params(self.language_params(None).await?)
             .get_json()
             .await?;
 
@@ -503,7 +2407,12 @@ impl AppleMusicClient {
 
     /// Get storefront information
     pub async fn get_storefront(&self) -> Result<Storefront> {
-        let path = format!("v1/storefronts/{}", self.config.storefront);
+        self.fetch_storefront(&self.storefront()).await
+    }
+
+    /// Get a storefront by ID
+    async fn fetch_storefront(&self, storefront: &str) -> Result<Storefront> {
+        let path = format!("v1/storefronts/{}", storefront);
         let response: ApiResponse<Storefront> = self.http_client.get_json(&path).await?;
 
         response
@@ -516,10 +2425,214 @@ impl AppleMusicClient {
             })
     }
 
-    /// Get all available storefronts
+    /// Get the storefront of the user the current user token belongs to
+    pub async fn get_user_storefront(&self) -> Result<Storefront> {
+        self.check_user_token()?;
+
+        let response: ApiResponse<Storefront> =
+            self.http_client.get_json("v1/me/storefront").await?;
+
+        response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppleMusicError::Api {
+                status: 404,
+                message: "Storefront not found".to_string(),
+            })
+    }
+
+    /// Get the storefront of the user a given user token belongs to
+    ///
+    /// The token is only used for this request, and is not refreshed if
+    /// Apple rejects it.
+    pub async fn get_storefront_for_user_token(&self, user_token: &str) -> Result<Storefront> {
+        Self::storefront_for_user_token(&self.http_client, user_token).await
+    }
+
+    /// Get the storefront of a user token through an HTTP client
+    async fn storefront_for_user_token(
+        http_client: &HttpClient,
+        user_token: &str,
+    ) -> Result<Storefront> {
+        let response: ApiResponse<Storefront> = http_client
+            .request("v1/me/storefront")
+            .user_token(Secret::new(user_token))
+            .get_json()
+            .await?;
+
+        response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppleMusicError::Api {
+                status: 404,
+                message: "Storefront not found".to_string(),
+            })
+    }
+
+    /// Get all available storefronts, following every page
     pub async fn get_storefronts(&self) -> Result<Vec<Storefront>> {
-        let response: ApiResponse<Storefront> = self.http_client.get_json("v1/storefronts").await?;
+        self.get_all_pages("v1/storefronts", Vec::new()).await
+    }
+
+    /// Build a storefront registry from the storefronts currently offered by the API
+    ///
+    /// Assign it to [`ClientConfig::storefront_registry`] to validate
+    /// configurations against it instead of the bundled list.
+    pub async fn refresh_storefront_registry(&self) -> Result<StorefrontRegistry> {
+        let storefronts = self.get_storefronts().await?;
+        Ok(StorefrontRegistry::from_storefronts(&storefronts))
+    }
+
+    /// Fetch a collection and every following page
+    ///
+    /// `params` are sent with each page unless the `next` link already
+    /// carries them.
+    async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: Vec<(String, String)>,
+    ) -> Result<Vec<T>> {
+        let mut data = Vec::new();
+        let mut next = Some(path.to_string());
+        let mut visited = HashSet::new();
+
+        while let Some(path) = next.take() {
+            visit_page(&mut visited, &path)?;
+            let response: ApiResponse<T> = self.get_page(&path, &params).await?;
+            data.extend(response.data);
+            next = response.next;
+        }
+
+        Ok(data)
+    }
+
+    /// Stream a collection and every following page, one resource at a time
+    ///
+    /// Pages are requested as the stream is consumed, with `params` sent as
+    /// in [`get_all_pages`](Self::get_all_pages).
+    fn stream_pages<T>(&self, path: String, params: Vec<(String, String)>) -> ResourceStream<'_, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        stream::try_unfold((Some(path), HashSet::new()), move |(next, mut visited)| {
+            let params = params.clone();
+            async move {
+                let Some(path) = next else {
+                    return Ok::<_, AppleMusicError>(None);
+                };
+                visit_page(&mut visited, &path)?;
+                let response: ApiResponse<T> = self.get_page(&path, &params).await?;
+                Ok(Some((response.data, (response.next, visited))))
+            }
+        })
+        .map_ok(|data| stream::iter(data.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Fetch one page of a collection
+    ///
+    /// `params` are sent unless the path, e.g. a `next` link, already
+    /// carries them.
+    async fn get_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(String, String)],
+    ) -> Result<ApiResponse<T>> {
+        let present: Vec<String> = path
+            .split_once('?')
+            .map(|(_, query)| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .map(|(key, _)| key.into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let page_params = params
+            .iter()
+            .filter(|(key, _)| !present.contains(key))
+            .cloned()
+            .collect();
+
+        self.http_client
+            .request(path)
+            .query_params(page_params)
+            .get_json()
+            .await
+    }
+}
+
+/// Record a page about to be requested, failing if it was requested before
+///
+/// A `next` link pointing back to an earlier page would otherwise be
+/// followed forever.
+fn visit_page(visited: &mut HashSet<String>, path: &str) -> Result<()> {
+    if visited.insert(path.to_string()) {
+        Ok(())
+    } else {
+        Err(AppleMusicError::Pagination(format!(
+            "next link loops back to {}",
+            path
+        )))
+    }
+}
+
+/// Use a stored, unexpired user token if the configuration has none
+fn load_stored_user_token(config: &mut ClientConfig, store: &dyn CredentialStore) -> Result<()> {
+    if config.user_token.is_none() {
+        config.user_token = store
+            .load(TokenKind::User)?
+            .filter(|stored| !stored.is_expired_at(config.clock.now_utc()))
+            .map(|stored| stored.token);
+    }
+    Ok(())
+}
+
+/// Save a developer token, which must be a JWT, unless it is already stored
+fn save_developer_token(store: &dyn CredentialStore, developer_token: &Secret) -> Result<()> {
+    let stored = store.load(TokenKind::Developer)?;
+    if stored.map(|stored| stored.token).as_ref() != Some(developer_token) {
+        store.save(
+            TokenKind::Developer,
+            &StoredToken::from_jwt(developer_token.clone())?,
+        )?;
+    }
+    Ok(())
+}
+
+/// Add a relationship to the `include` parameter, merging with any present
+fn add_include(params: &mut Vec<(String, String)>, relationship: &str) {
+    match params.iter_mut().find(|(key, _)| key == "include") {
+        Some((_, value)) => {
+            if !value.split(',').any(|existing| existing == relationship) {
+                value.push(',');
+                value.push_str(relationship);
+            }
+        }
+        None => params.push(("include".to_string(), relationship.to_string())),
+    }
+}
+
+/// Options for the `tracks` pages of a resource fetched with `options`
+///
+/// The language and sparse fieldsets carry over, while relationships,
+/// extensions and views describe the resource itself.
+fn tracks_options(options: &QueryOptions) -> QueryOptions {
+    QueryOptions {
+        fields: options.fields.clone(),
+        language: options.language.clone(),
+        ..QueryOptions::default()
+    }
+}
 
-        Ok(response.data)
+/// Barcode spellings sent to the API for a normalized UPC
+///
+/// Albums may be listed under the 12-digit UPC-A or the 13-digit EAN-13
+/// form, so both are queried when the code has a UPC-A form.
+fn upc_variants(upc: &str) -> Vec<String> {
+    match upc.strip_prefix('0') {
+        Some(upc_a) => vec![upc_a.to_string(), upc.to_string()],
+        None => vec![upc.to_string()],
     }
 }
@@ -0,0 +1,133 @@
+//! Time source abstraction for token expiry and rate limiting
+//!
+//! Everything that depends on the current time reads it from a [`Clock`], so
+//! expiry and refresh windows can be tested with a [`MockClock`].
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Get the current time
+    fn now(&self) -> SystemTime;
+
+    /// Get the current time as a UTC date
+    fn now_utc(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from(self.now())
+    }
+
+    /// Get a monotonic instant for measuring elapsed time
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock reading the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Manually driven clock for tests
+///
+/// Clones share the same time, so a test can keep one handle and advance it
+/// while another is used by the code under test. Its instants only move
+/// forward: setting the time backwards leaves them where they were.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+    start: Instant,
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: SystemTime,
+    elapsed: Duration,
+}
+
+impl MockClock {
+    /// Create a clock stopped at the given time
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                now,
+                elapsed: Duration::ZERO,
+            })),
+            start: Instant::now(),
+        }
+    }
+
+    /// Set the current time
+    pub fn set(&self, now: SystemTime) {
+        let mut state = self.state();
+        let forward = now.duration_since(state.now).unwrap_or_default();
+        state.elapsed += forward;
+        state.now = now;
+    }
+
+    /// Move the current time forward
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state();
+        state.now += duration;
+        state.elapsed += duration;
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        self.state().now
+    }
+
+    fn instant(&self) -> Instant {
+        self.start + self.state().elapsed
+    }
+}
+
+/// Cloneable handle to a [`Clock`], defaulting to the [`SystemClock`]
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    /// Wrap a clock
+    pub fn new<C: Clock + 'static>(clock: C) -> Self {
+        Self(Arc::new(clock))
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        Self::new(SystemClock)
+    }
+}
+
+impl Clock for SharedClock {
+    fn now(&self) -> SystemTime {
+        self.0.now()
+    }
+
+    fn instant(&self) -> Instant {
+        self.0.instant()
+    }
+}
+
+impl From<Arc<dyn Clock>> for SharedClock {
+    fn from(clock: Arc<dyn Clock>) -> Self {
+        Self(clock)
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
@@ -1,17 +1,90 @@
 //! Configuration for the Apple Music API client
 
+use crate::clock::{Clock, SharedClock};
+use crate::credentials::{CredentialStore, StoredToken, TokenKind};
 use crate::error::{AppleMusicError, Result};
+use crate::secret::Secret;
+use crate::storefronts::StorefrontRegistry;
 use derive_builder::Builder;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
 use std::time::Duration;
 
-#[derive(Debug, Builder, Clone, Default)]
+/// Environment variable holding a pre-generated developer token
+pub const ENV_DEVELOPER_TOKEN: &str = "APPLE_MUSIC_DEVELOPER_TOKEN";
+/// Environment variable holding the Team ID
+pub const ENV_TEAM_ID: &str = "APPLE_MUSIC_TEAM_ID";
+/// Environment variable holding the MusicKit key ID
+pub const ENV_KEY_ID: &str = "APPLE_MUSIC_KEY_ID";
+/// Environment variable holding the PEM private key
+pub const ENV_PRIVATE_KEY: &str = "APPLE_MUSIC_PRIVATE_KEY";
+/// Environment variable holding the path to the PEM private key
+pub const ENV_PRIVATE_KEY_PATH: &str = "APPLE_MUSIC_PRIVATE_KEY_PATH";
+/// Environment variable holding the storefront
+pub const ENV_STOREFRONT: &str = "APPLE_MUSIC_STOREFRONT";
+/// Environment variable holding the default language tag
+pub const ENV_LANGUAGE: &str = "APPLE_MUSIC_LANGUAGE";
+/// Environment variable holding the API base URL
+pub const ENV_BASE_URL: &str = "APPLE_MUSIC_BASE_URL";
+/// Environment variable holding the request timeout in seconds
+pub const ENV_TIMEOUT: &str = "APPLE_MUSIC_TIMEOUT";
+/// Environment variable holding the maximum number of retries
+pub const ENV_MAX_RETRIES: &str = "APPLE_MUSIC_MAX_RETRIES";
+/// Environment variable holding the proxy URL
+pub const ENV_PROXY: &str = "APPLE_MUSIC_PROXY";
+/// Environment variable holding the user token
+pub const ENV_USER_TOKEN: &str = "APPLE_MUSIC_USER_TOKEN";
+
+/// Configurable fields and the environment variables that set them
+pub(crate) const SETTINGS: &[(&str, &str)] = &[
+    ("developer_token", ENV_DEVELOPER_TOKEN),
+    ("team_id", ENV_TEAM_ID),
+    ("key_id", ENV_KEY_ID),
+    ("private_key", ENV_PRIVATE_KEY),
+    ("private_key_path", ENV_PRIVATE_KEY_PATH),
+    ("storefront", ENV_STOREFRONT),
+    ("language", ENV_LANGUAGE),
+    ("base_url", ENV_BASE_URL),
+    ("timeout", ENV_TIMEOUT),
+    ("max_retries", ENV_MAX_RETRIES),
+    ("proxy", ENV_PROXY),
+    ("user_token", ENV_USER_TOKEN),
+];
+
+/// Get the environment variable that sets a configurable field
+pub(crate) fn env_var_name(field: &str) -> &'static str {
+    SETTINGS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, variable)| *variable)
+        .unwrap_or_default()
+}
+
+/// A configuration value and a description of where it came from
+#[derive(Debug, Clone)]
+pub(crate) struct Setting {
+    pub value: String,
+    pub origin: String,
+}
+
+impl Setting {
+    pub fn new<V: Into<String>, O: Into<String>>(value: V, origin: O) -> Self {
+        Self {
+            value: value.into(),
+            origin: origin.into(),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Default)]
 #[builder(pattern = "owned", default)]
 pub struct ClientConfig {
     #[builder(default = "\"https://api.music.apple.com\".to_string()")]
     pub base_url: String,
 
-    #[builder(default)]
-    pub user_token: Option<String>,
+    #[builder(default, setter(into))]
+    pub user_token: Option<Secret>,
 
     #[builder(default = "Duration::from_secs(30)")]
     pub timeout: Duration,
@@ -28,11 +101,81 @@ pub struct ClientConfig {
     #[builder(default = "\"us\".to_string()")]
     pub storefront: String,
 
+    /// Default language tag for localized responses, e.g. `fr-CA`
+    #[builder(default)]
+    pub language: Option<String>,
+
+    /// Use the user's storefront for catalog requests once a user token is set
+    #[builder(default)]
+    pub use_user_storefront: bool,
+
+    /// Storefronts accepted by [`validate`](Self::validate), defaults to the bundled list
+    #[builder(default)]
+    pub storefront_registry: Option<StorefrontRegistry>,
+
+    /// Only check the storefront's format instead of looking it up in the registry
+    ///
+    /// Use this for storefronts Apple added after the bundled list was made.
+    #[builder(default)]
+    pub storefront_format_only: bool,
+
+    /// Proxy URL used for all requests
+    ///
+    /// Credentials in the URL are redacted from the configuration's `Debug` output.
+    #[builder(default)]
+    pub proxy: Option<String>,
+
+    /// Time source for token expiry, defaults to the system clock
+    #[builder(default)]
+    pub clock: SharedClock,
+
+    /// Name of the profile this configuration was loaded from
+    #[builder(default)]
+    pub profile: Option<String>,
+
     // Champs obligatoires (pas de #[builder(default)])
     pub team_id: String,
     pub key_id: String,
 
-    pub developer_token: String,
+    #[builder(setter(into))]
+    pub developer_token: Secret,
+}
+
+impl fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("base_url", &self.base_url)
+            .field("user_token", &self.user_token)
+            .field("timeout", &self.timeout)
+            .field("max_retries", &self.max_retries)
+            .field("retry_delay", &self.retry_delay)
+            .field("user_agent", &self.user_agent)
+            .field("storefront", &self.storefront)
+            .field("language", &self.language)
+            .field("use_user_storefront", &self.use_user_storefront)
+            .field("storefront_registry", &self.storefront_registry)
+            .field("storefront_format_only", &self.storefront_format_only)
+            .field("proxy", &self.proxy.as_deref().map(redact_proxy))
+            .field("clock", &self.clock)
+            .field("profile", &self.profile)
+            .field("team_id", &self.team_id)
+            .field("key_id", &self.key_id)
+            .field("developer_token", &self.developer_token)
+            .finish()
+    }
+}
+
+/// Replace the credentials of a proxy URL, keeping the rest readable
+fn redact_proxy(proxy: &str) -> String {
+    match url::Url::parse(proxy) {
+        Ok(mut url) if !url.username().is_empty() || url.password().is_some() => {
+            let _ = url.set_username("***");
+            let _ = url.set_password(None);
+            url.to_string()
+        }
+        Ok(_) => proxy.to_string(),
+        Err(_) => "[REDACTED]".to_string(),
+    }
 }
 
 impl ClientConfig {
@@ -53,33 +196,413 @@ impl ClientConfig {
             .unwrap())
     }
 
+    /// Create a new client configuration, reusing tokens from a credential store
+    ///
+    /// A stored developer token is reused if it was signed with `key_id` for
+    /// `team_id` and doesn't expire within 24 hours, otherwise a new one is
+    /// generated and saved. A stored user token that has not expired is
+    /// loaded as well.
+    pub fn from_credential_store<S: Into<String>>(
+        key_id: S,
+        team_id: S,
+        private_key: S,
+        store: &dyn CredentialStore,
+    ) -> Result<Self> {
+        Self::from_credential_store_with_clock(
+            key_id,
+            team_id,
+            private_key,
+            store,
+            SharedClock::default(),
+        )
+    }
+
+    /// Create a new client configuration from a credential store, checking
+    /// token expiry against the given clock
+    ///
+    /// The clock is also set on the returned configuration.
+    pub fn from_credential_store_with_clock<S: Into<String>>(
+        key_id: S,
+        team_id: S,
+        private_key: S,
+        store: &dyn CredentialStore,
+        clock: SharedClock,
+    ) -> Result<Self> {
+        let key_id = key_id.into();
+        let team_id = team_id.into();
+        let private_key = private_key.into();
+
+        let developer_token = match store.load(TokenKind::Developer)? {
+            Some(stored)
+                if stored.is_signed_by(&key_id, &team_id)
+                    && !stored.expires_within(clock.now_utc(), chrono::Duration::hours(24)) =>
+            {
+                stored.token
+            }
+            _ => {
+                let token = crate::auth::create_developer_token_with_clock(
+                    &team_id,
+                    &key_id,
+                    &private_key,
+                    &clock,
+                )?;
+                let stored = StoredToken::from_jwt(token)?;
+                store.save(TokenKind::Developer, &stored)?;
+                stored.token
+            }
+        };
+
+        let user_token = store
+            .load(TokenKind::User)?
+            .filter(|stored| !stored.is_expired_at(clock.now_utc()))
+            .map(|stored| stored.token);
+
+        Ok(ClientConfigBuilder::default()
+            .developer_token(developer_token)
+            .user_token(user_token)
+            .clock(clock)
+            .key_id(key_id)
+            .team_id(team_id)
+            .build()
+            .unwrap())
+    }
+
+    /// Create a client configuration from `APPLE_MUSIC_*` environment variables
+    ///
+    /// | Variable | Meaning |
+    /// |---|---|
+    /// | `APPLE_MUSIC_DEVELOPER_TOKEN` | Pre-generated developer token |
+    /// | `APPLE_MUSIC_TEAM_ID` | Team ID, required without a developer token |
+    /// | `APPLE_MUSIC_KEY_ID` | MusicKit key ID, required without a developer token |
+    /// | `APPLE_MUSIC_PRIVATE_KEY` | PEM private key (`\n` escapes allowed) |
+    /// | `APPLE_MUSIC_PRIVATE_KEY_PATH` | Path to the PEM private key |
+    /// | `APPLE_MUSIC_STOREFRONT` | Storefront, defaults to `us` |
+    /// | `APPLE_MUSIC_LANGUAGE` | Default language tag, e.g. `fr-CA` |
+    /// | `APPLE_MUSIC_BASE_URL` | API base URL |
+    /// | `APPLE_MUSIC_TIMEOUT` | Request timeout in seconds |
+    /// | `APPLE_MUSIC_MAX_RETRIES` | Maximum number of retries |
+    /// | `APPLE_MUSIC_PROXY` | Proxy URL for all requests |
+    /// | `APPLE_MUSIC_USER_TOKEN` | User token for personalized requests |
+    ///
+    /// Without a developer token, one is generated from the team ID, key ID
+    /// and exactly one of the private key variables. Every missing or invalid
+    /// variable is reported in a single error.
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_vars(std::env::vars())
+    }
+
+    /// Create a client configuration from the given variables, as [`from_env`](Self::from_env) does
+    pub fn from_env_vars<I, K, V>(vars: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+
+        let settings = SETTINGS
+            .iter()
+            .filter_map(|&(field, variable)| {
+                let value = vars.get(variable)?;
+                Some((field, Setting::new(value.clone(), variable)))
+            })
+            .collect();
+
+        Self::from_settings(
+            &settings,
+            &|field| env_var_name(field).to_string(),
+            "Invalid environment configuration",
+            None,
+        )
+    }
+
+    /// Create a client configuration from a profile in a TOML file
+    ///
+    /// The profile inherits `[profiles.default]` and is overridden by the
+    /// `APPLE_MUSIC_*` environment variables. Without a name, the profile is
+    /// taken from `APPLE_MUSIC_PROFILE`, then the file's `default_profile`,
+    /// then `default`. See [`profiles`](crate::profiles) for the file format.
+    pub fn from_file<P: AsRef<Path>>(path: P, profile: Option<&str>) -> Result<Self> {
+        Self::from_file_with_env_vars(path, profile, std::env::vars())
+    }
+
+    /// Create a client configuration from a profile in a TOML file, reading
+    /// overrides from the given variables instead of the process environment
+    pub fn from_file_with_env_vars<P, I, K, V>(
+        path: P,
+        profile: Option<&str>,
+        env_vars: I,
+    ) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            AppleMusicError::config(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        let env_vars = env_vars
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        crate::profiles::load_profile(
+            &contents,
+            &path.display().to_string(),
+            profile,
+            &env_vars,
+            path.parent(),
+        )
+    }
+
+    /// Create a client configuration from profile file contents, as [`from_file`](Self::from_file) does
+    ///
+    /// `env_vars` replaces the process environment, and relative key paths
+    /// are resolved against the current directory.
+    pub fn from_profile_str<I, K, V>(
+        contents: &str,
+        profile: Option<&str>,
+        env_vars: I,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let env_vars = env_vars
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        crate::profiles::load_profile(contents, "<profile file>", profile, &env_vars, None)
+    }
+
+    /// Build a configuration from named settings, collecting every problem
+    ///
+    /// `describe` names a setting in error messages when it is missing.
+    /// Relative key paths are resolved against `base_dir`.
+    pub(crate) fn from_settings(
+        settings: &HashMap<&'static str, Setting>,
+        describe: &dyn Fn(&str) -> String,
+        context: &str,
+        base_dir: Option<&Path>,
+    ) -> Result<Self> {
+        let get = |field: &str| {
+            settings
+                .get(field)
+                .map(|setting| (setting.value.trim(), setting.origin.as_str()))
+                .filter(|(value, _)| !value.is_empty())
+        };
+
+        let mut errors = Vec::new();
+        let mut builder = ClientConfigBuilder::default();
+
+        let team_id = get("team_id");
+        let key_id = get("key_id");
+        if let Some((team_id, _)) = team_id {
+            builder = builder.team_id(team_id.to_string());
+        }
+        if let Some((key_id, _)) = key_id {
+            builder = builder.key_id(key_id.to_string());
+        }
+
+        if let Some((developer_token, _)) = get("developer_token") {
+            builder = builder.developer_token(developer_token.to_string());
+        } else {
+            for (field, value) in [("team_id", team_id), ("key_id", key_id)] {
+                if value.is_none() {
+                    errors.push(format!(
+                        "{} is required when {} is not set",
+                        describe(field),
+                        describe("developer_token")
+                    ));
+                }
+            }
+
+            let private_key = match (get("private_key"), get("private_key_path")) {
+                (Some((_, key_origin)), Some((_, path_origin))) => {
+                    errors.push(format!(
+                        "{} and {} cannot both be set",
+                        key_origin, path_origin
+                    ));
+                    None
+                }
+                (Some((pem, origin)), None) => Some((pem.replace("\\n", "\n"), origin)),
+                (None, Some((path, origin))) => {
+                    let path = match base_dir {
+                        Some(base_dir) => base_dir.join(path),
+                        None => std::path::PathBuf::from(path),
+                    };
+                    match std::fs::read_to_string(&path) {
+                        Ok(pem) => Some((pem, origin)),
+                        Err(e) => {
+                            errors.push(format!(
+                                "{}: cannot read {}: {}",
+                                origin,
+                                path.display(),
+                                e
+                            ));
+                            None
+                        }
+                    }
+                }
+                (None, None) => {
+                    errors.push(format!(
+                        "{} or {} is required when {} is not set",
+                        describe("private_key"),
+                        describe("private_key_path"),
+                        describe("developer_token")
+                    ));
+                    None
+                }
+            };
+
+            if let (Some((team_id, _)), Some((key_id, _)), Some((private_key, origin))) =
+                (team_id, key_id, private_key)
+            {
+                match crate::auth::create_developer_token(team_id, key_id, &private_key) {
+                    Ok(token) => builder = builder.developer_token(token),
+                    Err(e) => errors.push(format!("{}: invalid private key: {}", origin, e)),
+                }
+            }
+        }
+
+        if let Some((storefront, origin)) = get("storefront") {
+            match StorefrontRegistry::bundled().validate(storefront) {
+                Ok(storefront) => builder = builder.storefront(storefront),
+                Err(e) => errors.push(format!("{}: {}", origin, e)),
+            }
+        }
+
+        if let Some((language, origin)) = get("language") {
+            match crate::utils::parse_language_tag(language) {
+                Ok(language) => builder = builder.language(Some(language)),
+                Err(e) => errors.push(format!("{}: {}", origin, e)),
+            }
+        }
+
+        if let Some((base_url, origin)) = get("base_url") {
+            match url::Url::parse(base_url) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {
+                    builder = builder.base_url(base_url.trim_end_matches('/').to_string())
+                }
+                _ => errors.push(format!(
+                    "{}: {:?} is not an http:// or https:// URL",
+                    origin, base_url
+                )),
+            }
+        }
+
+        if let Some((timeout, origin)) = get("timeout") {
+            match timeout.parse::<u64>() {
+                Ok(seconds) if seconds > 0 => {
+                    builder = builder.timeout(Duration::from_secs(seconds))
+                }
+                _ => errors.push(format!(
+                    "{}: {:?} is not a positive number of seconds",
+                    origin, timeout
+                )),
+            }
+        }
+
+        if let Some((max_retries, origin)) = get("max_retries") {
+            match max_retries.parse::<u32>() {
+                Ok(max_retries) => builder = builder.max_retries(max_retries),
+                Err(_) => errors.push(format!(
+                    "{}: {:?} is not a non-negative integer",
+                    origin, max_retries
+                )),
+            }
+        }
+
+        if let Some((proxy, origin)) = get("proxy") {
+            match reqwest::Proxy::all(proxy) {
+                Ok(_) => builder = builder.proxy(Some(proxy.to_string())),
+                Err(e) => errors.push(format!("{}: {}", origin, e)),
+            }
+        }
+
+        if let Some((user_token, _)) = get("user_token") {
+            builder = builder.user_token(Some(Secret::new(user_token)));
+        }
+
+        if !errors.is_empty() {
+            return Err(AppleMusicError::config(format!(
+                "{}:\n  - {}",
+                context,
+                errors.join("\n  - ")
+            )));
+        }
+
+        builder
+            .build()
+            .map_err(|e| AppleMusicError::config(e.to_string()))
+    }
+
     /// Validate the configuration
+    ///
+    /// Errors name the profile and field when the configuration was loaded
+    /// from a profile.
     pub fn validate(&self) -> Result<()> {
+        let error = |field: &str, message: &str| match &self.profile {
+            Some(profile) => AppleMusicError::config(format!(
+                "profile {:?}, field {:?}: {}",
+                profile, field, message
+            )),
+            None => AppleMusicError::config(message),
+        };
+
         if self.developer_token.is_empty() {
-            return Err(AppleMusicError::config("Developer token is required"));
+            return Err(error("developer_token", "Developer token is required"));
         }
 
         if self.base_url.is_empty() {
-            return Err(AppleMusicError::config("Base URL cannot be empty"));
+            return Err(error("base_url", "Base URL cannot be empty"));
         }
 
         if self.storefront.is_empty() {
-            return Err(AppleMusicError::config("Storefront cannot be empty"));
+            return Err(error("storefront", "Storefront cannot be empty"));
+        }
+
+        if let Err(e) = self.check_storefront(&self.storefront) {
+            let message = match e {
+                AppleMusicError::InvalidRequest(message) => message,
+                e => e.to_string(),
+            };
+            return Err(error("storefront", &message));
         }
 
         // Validate base URL format
         if !self.base_url.starts_with("http") {
-            return Err(AppleMusicError::config(
+            return Err(error(
+                "base_url",
                 "Base URL must start with http:// or https://",
             ));
         }
 
         Ok(())
     }
+
+    /// Check a storefront against the configured or bundled registry
+    ///
+    /// With [`storefront_format_only`](Self::storefront_format_only) only the
+    /// format is checked. Returns the normalized storefront ID.
+    pub(crate) fn check_storefront(&self, storefront: &str) -> Result<String> {
+        if self.storefront_format_only {
+            return crate::utils::parse_storefront(storefront);
+        }
+        match &self.storefront_registry {
+            Some(registry) => registry.validate(storefront),
+            None => StorefrontRegistry::bundled().validate(storefront),
+        }
+    }
 }
 
 /// Media types supported by the Apple Music API
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MediaType {
     Songs,
     Albums,
@@ -138,6 +661,9 @@ pub struct SearchOptions {
 
     /// Media types to search in
     pub types: Vec<MediaType>,
+
+    /// Language tag overriding the client's language
+    pub language: Option<String>,
 }
 
 impl SearchOptions {
@@ -163,4 +689,209 @@ impl SearchOptions {
         self.types = types;
         self
     }
+
+    /// Set the language of the results, e.g. `fr-CA`
+    pub fn with_language<S: Into<String>>(mut self, language: S) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+}
+
+/// Chart options for API requests
+#[derive(Debug, Clone, Default)]
+pub struct ChartOptions {
+    /// Media types to fetch charts for
+    pub types: Vec<MediaType>,
+
+    /// Chart kind, e.g. `most-played`
+    pub chart: Option<String>,
+
+    /// Genre ID to restrict the charts to
+    pub genre: Option<String>,
+
+    /// Limit the number of entries per chart
+    pub limit: Option<u32>,
+
+    /// Offset for pagination
+    pub offset: Option<u32>,
+
+    /// Language tag overriding the client's language
+    pub language: Option<String>,
+}
+
+impl ChartOptions {
+    /// Create chart options for the given media types
+    pub fn new(types: Vec<MediaType>) -> Self {
+        Self {
+            types,
+            ..Self::default()
+        }
+    }
+
+    /// Set the chart kind
+    pub fn with_chart<S: Into<String>>(mut self, chart: S) -> Self {
+        self.chart = Some(chart.into());
+        self
+    }
+
+    /// Restrict the charts to a genre
+    pub fn with_genre<S: Into<String>>(mut self, genre: S) -> Self {
+        self.genre = Some(genre.into());
+        self
+    }
+
+    /// Set the entry limit per chart
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the offset for pagination
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Set the language of the charts, e.g. `fr-CA`
+    pub fn with_language<S: Into<String>>(mut self, language: S) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+}
+
+/// Query options shaping the resources returned by fetch requests
+///
+/// Maps to the `include`, `extend`, `fields[...]`, `views` and `with` query
+/// parameters. Relationships and views requested this way are returned in
+/// the resources' `relationships` and `views` fields, and extended
+/// attributes in their `attributes`. Pass them to the `*_with_options` fetch
+/// methods of [`AppleMusicClient`](crate::AppleMusicClient):
+///
+/// ```rust,no_run
+/// # use apple_music_api::{AppleMusicClient, config::QueryOptions};
+/// # async fn example(client: AppleMusicClient) -> apple_music_api::error::Result<()> {
+/// let options = QueryOptions::new().with_include(&["artists"]).with_extend(&["artistUrl"]);
+/// let song = client.get_song_with_options("1440857786", &options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// Relationships to include, e.g. `tracks`
+    pub include: Vec<String>,
+
+    /// Extended attributes to add, e.g. `editorialArtwork`
+    pub extend: Vec<String>,
+
+    /// Attributes to return, by resource type, e.g. `songs` => `name`
+    ///
+    /// Only catalog resources support sparse fieldsets. Attributes left out
+    /// of the response keep their default values.
+    pub fields: Vec<(String, Vec<String>)>,
+
+    /// Views to include, e.g. `top-songs`
+    pub views: Vec<String>,
+
+    /// Additional data to return, e.g. `topResults`
+    pub with: Vec<String>,
+
+    /// Language tag overriding the client's language
+    ///
+    /// The client checks it against the storefront and sends it as `l`, so
+    /// it is not part of [`to_params`](Self::to_params).
+    pub language: Option<String>,
+}
+
+impl QueryOptions {
+    /// Create empty query options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add relationships to include
+    pub fn with_include(mut self, include: &[&str]) -> Self {
+        extend_unique(&mut self.include, include);
+        self
+    }
+
+    /// Add extended attributes
+    pub fn with_extend(mut self, extend: &[&str]) -> Self {
+        extend_unique(&mut self.extend, extend);
+        self
+    }
+
+    /// Restrict the attributes returned for a resource type
+    pub fn with_fields(mut self, resource_type: &str, fields: &[&str]) -> Self {
+        match self
+            .fields
+            .iter_mut()
+            .find(|(existing, _)| existing == resource_type)
+        {
+            Some((_, existing)) => extend_unique(existing, fields),
+            None => {
+                let mut values = Vec::new();
+                extend_unique(&mut values, fields);
+                self.fields.push((resource_type.to_string(), values));
+            }
+        }
+        self
+    }
+
+    /// Add views to include
+    pub fn with_views(mut self, views: &[&str]) -> Self {
+        extend_unique(&mut self.views, views);
+        self
+    }
+
+    /// Add values to the `with` parameter
+    pub fn with_with(mut self, with: &[&str]) -> Self {
+        extend_unique(&mut self.with, with);
+        self
+    }
+
+    /// Set the language of the results, e.g. `fr-CA`
+    pub fn with_language<S: Into<String>>(mut self, language: S) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Check if no option is set
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty()
+            && self.extend.is_empty()
+            && self.fields.is_empty()
+            && self.views.is_empty()
+            && self.with.is_empty()
+            && self.language.is_none()
+    }
+
+    /// Get the query parameters for these options
+    pub fn to_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if !self.include.is_empty() {
+            params.push(("include".to_string(), self.include.join(",")));
+        }
+        if !self.extend.is_empty() {
+            params.push(("extend".to_string(), self.extend.join(",")));
+        }
+        for (resource_type, fields) in &self.fields {
+            params.push((format!("fields[{}]", resource_type), fields.join(",")));
+        }
+        if !self.views.is_empty() {
+            params.push(("views".to_string(), self.views.join(",")));
+        }
+        if !self.with.is_empty() {
+            params.push(("with".to_string(), self.with.join(",")));
+        }
+        params
+    }
+}
+
+/// Append values that are not already present
+fn extend_unique(values: &mut Vec<String>, new: &[&str]) {
+    for value in new {
+        if !values.iter().any(|existing| existing == value) {
+            values.push(value.to_string());
+        }
+    }
 }
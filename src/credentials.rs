@@ -0,0 +1,287 @@
+//! Persistent storage for developer and user tokens
+//!
+//! A [`CredentialStore`] lets [`ClientConfig`](crate::ClientConfig) and
+//! [`AppleMusicClient`](crate::AppleMusicClient) reuse tokens across restarts
+//! instead of minting a new developer token or asking the user to authorize
+//! again every time.
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::{AppleMusicError, Result};
+use crate::secret::Secret;
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Kind of token held by a credential store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    /// JWT developer token
+    Developer,
+
+    /// Music-User-Token for personalized requests
+    User,
+}
+
+/// A token together with its issue and expiry times
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredToken {
+    /// The token value
+    pub token: Secret,
+
+    /// When the token was issued
+    pub issued_at: DateTime<Utc>,
+
+    /// When the token expires, if known
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredToken {
+    /// Create a new stored token
+    pub fn new<S: Into<Secret>>(
+        token: S,
+        issued_at: DateTime<Utc>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            token: token.into(),
+            issued_at,
+            expires_at,
+        }
+    }
+
+    /// Create a stored token from a developer token, reading `iat` and `exp` from its claims
+    pub fn from_jwt<S: Into<Secret>>(token: S) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct TimeClaims {
+            iat: i64,
+            exp: Option<i64>,
+        }
+
+        let token: Secret = token.into();
+        let claims: TimeClaims = decode_jwt_segment(token.expose(), 1)?;
+
+        let timestamp = |secs: i64| {
+            Utc.timestamp_opt(secs, 0)
+                .single()
+                .ok_or_else(|| AppleMusicError::auth("Invalid JWT timestamp"))
+        };
+
+        Ok(Self {
+            issued_at: timestamp(claims.iat)?,
+            expires_at: claims.exp.map(timestamp).transpose()?,
+            token,
+        })
+    }
+
+    /// Check if a developer token was signed with the given key ID for the given team
+    ///
+    /// Compares the JWT's `kid` header and `iss` claim. A token that is not a
+    /// JWT never matches.
+    pub fn is_signed_by(&self, key_id: &str, team_id: &str) -> bool {
+        #[derive(Deserialize)]
+        struct KeyHeader {
+            kid: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct IssuerClaims {
+            iss: Option<String>,
+        }
+
+        let header = decode_jwt_segment::<KeyHeader>(self.token.expose(), 0);
+        let claims = decode_jwt_segment::<IssuerClaims>(self.token.expose(), 1);
+        match (header, claims) {
+            (Ok(header), Ok(claims)) => {
+                header.kid.as_deref() == Some(key_id) && claims.iss.as_deref() == Some(team_id)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if the token expires before `now + margin`
+    pub fn expires_within(&self, now: DateTime<Utc>, margin: chrono::Duration) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now + margin >= expires_at)
+    }
+
+    /// Check if the token is expired at the given time
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_within(now, chrono::Duration::zero())
+    }
+
+    /// Check if the token is expired
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(SystemClock.now_utc())
+    }
+}
+
+/// Storage backend for developer and user tokens
+pub trait CredentialStore: Send + Sync {
+    /// Load a token, returning `None` if none is stored
+    fn load(&self, kind: TokenKind) -> Result<Option<StoredToken>>;
+
+    /// Save a token, replacing any previous one of the same kind
+    fn save(&self, kind: TokenKind, token: &StoredToken) -> Result<()>;
+
+    /// Remove a stored token
+    fn clear(&self, kind: TokenKind) -> Result<()>;
+}
+
+/// In-memory credential store, mainly useful for tests
+#[derive(Debug, Default)]
+pub struct MemoryCredentialStore {
+    tokens: Mutex<HashMap<TokenKind, StoredToken>>,
+}
+
+impl MemoryCredentialStore {
+    /// Create an empty in-memory store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CredentialStore for MemoryCredentialStore {
+    fn load(&self, kind: TokenKind) -> Result<Option<StoredToken>> {
+        Ok(lock(&self.tokens)?.get(&kind).cloned())
+    }
+
+    fn save(&self, kind: TokenKind, token: &StoredToken) -> Result<()> {
+        lock(&self.tokens)?.insert(kind, token.clone());
+        Ok(())
+    }
+
+    fn clear(&self, kind: TokenKind) -> Result<()> {
+        lock(&self.tokens)?.remove(&kind);
+        Ok(())
+    }
+}
+
+/// Credential store backed by a JSON file readable only by its owner (mode 0600)
+#[derive(Debug)]
+pub struct FileCredentialStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+/// On-disk layout of a [`FileCredentialStore`]
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    developer: Option<StoredToken>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<StoredToken>,
+}
+
+impl CredentialFile {
+    fn slot(&mut self, kind: TokenKind) -> &mut Option<StoredToken> {
+        match kind {
+            TokenKind::Developer => &mut self.developer,
+            TokenKind::User => &mut self.user,
+        }
+    }
+}
+
+impl FileCredentialStore {
+    /// Create a store that reads and writes the given file
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Get the path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<CredentialFile> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CredentialFile::default()),
+            Err(e) => Err(io_error(&self.path, e)),
+        }
+    }
+
+    fn write(&self, file: &CredentialFile) -> Result<()> {
+        let contents = serde_json::to_vec_pretty(file)?;
+
+        // Write to a sibling file first so a crash never leaves a truncated store
+        let tmp_path = self.path.with_extension("tmp");
+        write_private(&tmp_path, &contents).map_err(|e| io_error(&tmp_path, e))?;
+        std::fs::rename(&tmp_path, &self.path).map_err(|e| io_error(&self.path, e))
+    }
+
+    fn update(&self, kind: TokenKind, token: Option<&StoredToken>) -> Result<()> {
+        let _guard = lock(&self.lock)?;
+        let mut file = self.read()?;
+        *file.slot(kind) = token.cloned();
+        self.write(&file)
+    }
+}
+
+impl CredentialStore for FileCredentialStore {
+    fn load(&self, kind: TokenKind) -> Result<Option<StoredToken>> {
+        let _guard = lock(&self.lock)?;
+        Ok(self.read()?.slot(kind).take())
+    }
+
+    fn save(&self, kind: TokenKind, token: &StoredToken) -> Result<()> {
+        self.update(kind, Some(token))
+    }
+
+    fn clear(&self, kind: TokenKind) -> Result<()> {
+        self.update(kind, None)
+    }
+}
+
+/// Write a file that only its owner can read or write
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies on creation, so tighten an existing file as well
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// Write a file that only its owner can read or write
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> AppleMusicError {
+    AppleMusicError::config(format!("Credential store {}: {}", path.display(), error))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<std::sync::MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| AppleMusicError::config("Credential store lock poisoned"))
+}
+
+/// Decode the JSON in a segment of a JWT, `0` for the header and `1` for the claims
+fn decode_jwt_segment<T: serde::de::DeserializeOwned>(token: &str, index: usize) -> Result<T> {
+    let segment = token
+        .split('.')
+        .nth(index)
+        .ok_or_else(|| AppleMusicError::auth("Developer token is not a JWT"))?;
+    let segment = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(segment.trim_end_matches('='))
+        .map_err(|e| AppleMusicError::auth(format!("Invalid JWT segment: {}", e)))?;
+    Ok(serde_json::from_slice(&segment)?)
+}
@@ -13,6 +13,10 @@ pub enum AppleMusicError {
     #[error("Authentication error: {0}")]
     Auth(String),
 
+    /// The user token was rejected because it expired or was revoked
+    #[error("User token rejected: {status} - {message}")]
+    UserTokenExpired { status: u16, message: String },
+
     /// API errors returned by Apple Music
     #[error("API error: {status} - {message}")]
     Api { status: u16, message: String },
@@ -44,6 +48,10 @@ pub enum AppleMusicError {
     /// Rate limiting errors
     #[error("Rate limit exceeded: {0}")]
     RateLimit(String),
+
+    /// A paginated response linked back to a page already fetched
+    #[error("Pagination error: {0}")]
+    Pagination(String),
 }
 
 /// Result type alias for Apple Music operations
@@ -93,13 +101,18 @@ impl AppleMusicError {
         Self::InvalidRequest(message.into())
     }
 
+    /// Check if the error means the user token must be renewed
+    pub fn is_user_token_expired(&self) -> bool {
+        matches!(self, Self::UserTokenExpired { .. })
+    }
+
     /// Check if the error is retryable
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::Http(err) => {
                 err.is_timeout()
                     || err.is_connect()
-                    || err.status().map_or(false, |s| s.is_server_error())
+                    || err.status().is_some_and(|s| s.is_server_error())
             }
             Self::RateLimit(_) => true,
             Self::Timeout(_) => true,
@@ -111,7 +124,7 @@ impl AppleMusicError {
     pub fn status_code(&self) -> Option<u16> {
         match self {
             Self::Http(err) => err.status().map(|s| s.as_u16()),
-            Self::Api { status, .. } => Some(*status),
+            Self::Api { status, .. } | Self::UserTokenExpired { status, .. } => Some(*status),
             _ => None,
         }
     }
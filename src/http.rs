@@ -1,82 +1,180 @@
 //! HTTP client implementation with rusttls for Apple Music API
 
+use crate::clock::{Clock, SharedClock};
 use crate::config::ClientConfig;
+use crate::credentials::StoredToken;
 use crate::error::{AppleMusicError, Result};
+use crate::secret::Secret;
+use chrono::{DateTime, Utc};
 use reqwest::{Client, ClientBuilder, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// Future returned by a user token refresh hook
+pub type UserTokenRefreshFuture = Pin<Box<dyn Future<Output = Result<String>> + Send>>;
+
+/// Hook called to obtain a new user token after the current one was rejected
+pub type UserTokenRefresher = Arc<dyn Fn() -> UserTokenRefreshFuture + Send + Sync>;
+
+/// Future returned by a developer token refresh hook
+pub type DeveloperTokenRefreshFuture = Pin<Box<dyn Future<Output = Result<Secret>> + Send>>;
+
+/// Hook called to obtain a new developer token before the current one expires
+pub type DeveloperTokenRefresher = Arc<dyn Fn() -> DeveloperTokenRefreshFuture + Send + Sync>;
+
+/// How long before its expiry the developer token is refreshed
+const DEVELOPER_TOKEN_REFRESH_MARGIN: chrono::Duration = chrono::Duration::hours(24);
 
 /// HTTP client wrapper for Apple Music API requests
 pub struct HttpClient {
     client: Client,
     base_url: String,
-    developer_token: String,
-    user_token: Option<String>,
+    clock: SharedClock,
+    developer_token: RwLock<Secret>,
+    developer_token_expires_at: RwLock<Option<DateTime<Utc>>>,
+    developer_token_refresher: RwLock<Option<DeveloperTokenRefresher>>,
+    developer_refresh_lock: tokio::sync::Mutex<()>,
+    user_token: RwLock<Option<Secret>>,
+    user_token_refresher: RwLock<Option<UserTokenRefresher>>,
+    refresh_lock: tokio::sync::Mutex<()>,
 }
 
 impl HttpClient {
     /// Create a new HTTP client with the given configuration
     pub fn new(config: &ClientConfig) -> Result<Self> {
         // Configure rustls client
-        let client = ClientBuilder::new()
+        let mut builder = ClientBuilder::new()
             .use_rustls_tls()
             .timeout(config.timeout)
-            .user_agent(&config.user_agent)
-            .build()
-            .map_err(AppleMusicError::Http)?;
+            .user_agent(&config.user_agent);
+
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(AppleMusicError::Http)?);
+        }
+
+        let client = builder.build().map_err(AppleMusicError::Http)?;
 
         Ok(Self {
             client,
             base_url: config.base_url.clone(),
-            developer_token: config.developer_token.clone(),
-            user_token: config.user_token.clone(),
+            clock: config.clock.clone(),
+            developer_token: RwLock::new(config.developer_token.clone()),
+            developer_token_expires_at: RwLock::new(jwt_expiry(&config.developer_token)),
+            developer_token_refresher: RwLock::new(None),
+            developer_refresh_lock: tokio::sync::Mutex::new(()),
+            user_token: RwLock::new(config.user_token.clone()),
+            user_token_refresher: RwLock::new(None),
+            refresh_lock: tokio::sync::Mutex::new(()),
         })
     }
 
     /// Execute a GET request
     pub async fn get(&self, path: &str) -> Result<Response> {
         let url = self.build_url(path)?;
-        let mut request = self.client.get(&url);
-
-        // Add authentication headers
-        request = self.add_auth_headers(request);
-
-        let response = request.send().await.map_err(AppleMusicError::Http)?;
-        self.handle_response(response).await
+        self.send(path, || self.client.get(&url)).await
     }
 
     /// Execute a POST request with JSON body
     pub async fn post<T: serde::Serialize>(&self, path: &str, body: &T) -> Result<Response> {
         let url = self.build_url(path)?;
-        let mut request = self.client.post(&url).json(body);
-
-        // Add authentication headers
-        request = self.add_auth_headers(request);
-
-        let response = request.send().await.map_err(AppleMusicError::Http)?;
-        self.handle_response(response).await
+        self.send(path, || self.client.post(&url).json(body)).await
     }
 
     /// Execute a PUT request with JSON body
     pub async fn put<T: serde::Serialize>(&self, path: &str, body: &T) -> Result<Response> {
         let url = self.build_url(path)?;
-        let mut request = self.client.put(&url).json(body);
-
-        // Add authentication headers
-        request = self.add_auth_headers(request);
-
-        let response = request.send().await.map_err(AppleMusicError::Http)?;
-        self.handle_response(response).await
+        self.send(path, || self.client.put(&url).json(body)).await
     }
 
     /// Execute a DELETE request
     pub async fn delete(&self, path: &str) -> Result<Response> {
         let url = self.build_url(path)?;
-        let mut request = self.client.delete(&url);
+        self.send(path, || self.client.delete(&url)).await
+    }
+
+    /// Send a request, refreshing the user token once if it was rejected
+    async fn send<F>(&self, path: &str, build: F) -> Result<Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        self.refresh_developer_token().await?;
+
+        let sent_user_token = self.user_token();
+        let response = self
+            .add_auth_headers(build(), sent_user_token.as_ref().map(Secret::expose))
+            .send()
+            .await
+            .map_err(AppleMusicError::Http)?;
+
+        match self
+            .handle_response(response, path, sent_user_token.is_some())
+            .await
+        {
+            Err(AppleMusicError::UserTokenExpired { status, message }) => {
+                let Some(user_token) = self.refresh_user_token(sent_user_token.as_ref()).await?
+                else {
+                    return Err(AppleMusicError::UserTokenExpired { status, message });
+                };
+
+                let response = self
+                    .add_auth_headers(build(), Some(user_token.expose()))
+                    .send()
+                    .await
+                    .map_err(AppleMusicError::Http)?;
+                self.handle_response(response, path, true).await
+            }
+            result => result,
+        }
+    }
+
+    /// Obtain a new user token from the refresh hook, if one is registered
+    ///
+    /// Concurrent requests rejected with the same token share a single call
+    /// to the hook.
+    async fn refresh_user_token(&self, rejected: Option<&Secret>) -> Result<Option<Secret>> {
+        let Some(refresher) = read_lock(&self.user_token_refresher).clone() else {
+            return Ok(None);
+        };
+
+        let _guard = self.refresh_lock.lock().await;
+        let current = self.user_token();
+        if current.is_some() && current.as_ref() != rejected {
+            // Another request refreshed the token while we were waiting
+            return Ok(current);
+        }
+
+        let user_token = Secret::new(refresher().await?);
+        self.set_user_token(Some(user_token.clone()));
+        Ok(Some(user_token))
+    }
+
+    /// Refresh the developer token through its hook if it expires within a day
+    ///
+    /// Concurrent requests share a single call to the hook.
+    async fn refresh_developer_token(&self) -> Result<()> {
+        if !self.developer_token_expiring() {
+            return Ok(());
+        }
+        let Some(refresher) = read_lock(&self.developer_token_refresher).clone() else {
+            return Ok(());
+        };
+
+        let _guard = self.developer_refresh_lock.lock().await;
+        if !self.developer_token_expiring() {
+            // Another request refreshed the token while we were waiting
+            return Ok(());
+        }
 
-        // Add authentication headers
-        request = self.add_auth_headers(request);
+        self.set_developer_token(refresher().await?);
+        Ok(())
+    }
 
-        let response = request.send().await.map_err(AppleMusicError::Http)?;
-        self.handle_response(response).await
+    /// Check if the developer token expires within the refresh margin
+    fn developer_token_expiring(&self) -> bool {
+        read_lock(&self.developer_token_expires_at).is_some_and(|expires_at| {
+            self.clock.now_utc() + DEVELOPER_TOKEN_REFRESH_MARGIN > expires_at
+        })
     }
 
     /// Build a full URL from a path
@@ -90,14 +188,21 @@ impl HttpClient {
     }
 
     /// Add authentication headers to a request
-    fn add_auth_headers(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    fn add_auth_headers(
+        &self,
+        request: reqwest::RequestBuilder,
+        user_token: Option<&str>,
+    ) -> reqwest::RequestBuilder {
         let mut request = request
-            .header("Authorization", format!("Bearer {}", self.developer_token))
+            .header(
+                "Authorization",
+                format!("Bearer {}", read_lock(&self.developer_token).expose()),
+            )
             .header("Accept", "application/json")
             .header("Content-Type", "application/json");
 
         // Add user token if available (for personalized requests)
-        if let Some(user_token) = &self.user_token {
+        if let Some(user_token) = user_token {
             request = request.header("Music-User-Token", user_token);
         }
 
@@ -105,7 +210,18 @@ impl HttpClient {
     }
 
     /// Handle API response and check for errors
-    async fn handle_response(&self, response: Response) -> Result<Response> {
+    ///
+    /// A 401 or 403 on a `v1/me` endpoint, returned for a request that
+    /// carried a user token, is reported as
+    /// [`AppleMusicError::UserTokenExpired`]. Elsewhere these mean the
+    /// developer token or the storefront was rejected and stay
+    /// [`AppleMusicError::Api`].
+    async fn handle_response(
+        &self,
+        response: Response,
+        path: &str,
+        sent_user_token: bool,
+    ) -> Result<Response> {
         let status = response.status();
 
         if status.is_success() {
@@ -114,22 +230,20 @@ impl HttpClient {
             // Try to parse error response
             let error_text = response.text().await.unwrap_or_default();
 
-            if let Ok(error_response) =
-                serde_json::from_str::<crate::error::ApiErrorResponse>(&error_text)
-            {
-                if let Some(error) = error_response.errors.first() {
-                    return Err(AppleMusicError::Api {
-                        status: status.as_u16(),
-                        message: error.detail.clone(),
-                    });
-                }
+            let message = serde_json::from_str::<crate::error::ApiErrorResponse>(&error_text)
+                .ok()
+                .and_then(|error_response| error_response.errors.into_iter().next())
+                .map(|error| error.detail)
+                // Fallback to the raw body
+                .unwrap_or(error_text);
+
+            let status = status.as_u16();
+            let user_endpoint = path.trim_start_matches('/').starts_with("v1/me");
+            if sent_user_token && user_endpoint && (status == 401 || status == 403) {
+                return Err(AppleMusicError::UserTokenExpired { status, message });
             }
 
-            // Fallback to generic API error
-            Err(AppleMusicError::Api {
-                status: status.as_u16(),
-                message: error_text,
-            })
+            Err(AppleMusicError::Api { status, message })
         }
     }
 
@@ -159,20 +273,60 @@ impl HttpClient {
         response.json().await.map_err(AppleMusicError::Http)
     }
 
+    /// Replace the developer token sent with subsequent requests
+    pub fn set_developer_token(&self, developer_token: Secret) {
+        *write_lock(&self.developer_token_expires_at) = jwt_expiry(&developer_token);
+        *write_lock(&self.developer_token) = developer_token;
+    }
+
+    /// Register a hook that provides a new developer token before the current one expires
+    ///
+    /// The hook is called before a request when the developer token expires
+    /// within 24 hours. Tokens that are not JWTs are never refreshed.
+    pub fn set_developer_token_refresher(&self, refresher: Option<DeveloperTokenRefresher>) {
+        *write_lock(&self.developer_token_refresher) = refresher;
+    }
+
+    /// Get the developer token sent with requests
+    pub fn developer_token(&self) -> Secret {
+        read_lock(&self.developer_token).clone()
+    }
+
     /// Update the user token
-    pub fn set_user_token(&mut self, user_token: Option<String>) {
-        self.user_token = user_token;
+    pub fn set_user_token(&self, user_token: Option<Secret>) {
+        *write_lock(&self.user_token) = user_token;
     }
 
     /// Get the current user token
-    pub fn user_token(&self) -> Option<&str> {
-        self.user_token.as_deref()
+    pub fn user_token(&self) -> Option<Secret> {
+        read_lock(&self.user_token).clone()
     }
 
     /// Check if user token is set
     pub fn has_user_token(&self) -> bool {
-        self.user_token.is_some()
+        read_lock(&self.user_token).is_some()
     }
+
+    /// Register a hook that provides a new user token when the current one is rejected
+    pub fn set_user_token_refresher(&self, refresher: Option<UserTokenRefresher>) {
+        *write_lock(&self.user_token_refresher) = refresher;
+    }
+}
+
+/// Read the expiry of a developer token, if it is a JWT
+fn jwt_expiry(token: &Secret) -> Option<DateTime<Utc>> {
+    StoredToken::from_jwt(token.clone())
+        .ok()
+        .and_then(|stored| stored.expires_at)
+}
+
+fn read_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
 /// Request builder for complex requests
@@ -181,6 +335,7 @@ pub struct RequestBuilder<'a> {
     path: String,
     query_params: Vec<(String, String)>,
     headers: Vec<(String, String)>,
+    user_token: Option<Secret>,
 }
 
 impl<'a> RequestBuilder<'a> {
@@ -191,6 +346,7 @@ impl<'a> RequestBuilder<'a> {
             path: path.into(),
             query_params: Vec::new(),
             headers: Vec::new(),
+            user_token: None,
         }
     }
 
@@ -212,12 +368,20 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// Send the request with this user token instead of the client's
+    ///
+    /// A rejected token is reported as is, without calling the refresh hook.
+    pub fn user_token(mut self, user_token: Secret) -> Self {
+        self.user_token = Some(user_token);
+        self
+    }
+
     /// Build the URL with query parameters
     fn build_url(&self) -> Result<String> {
         let mut url = self.client.build_url(&self.path)?;
 
         if !self.query_params.is_empty() {
-            url.push('?');
+            url.push(if url.contains('?') { '&' } else { '?' });
             let query_string = self
                 .query_params
                 .iter()
@@ -233,18 +397,32 @@ impl<'a> RequestBuilder<'a> {
     /// Execute GET request
     pub async fn get(self) -> Result<Response> {
         let url = self.build_url()?;
-        let mut request = self.client.client.get(&url);
+        let build = || {
+            let mut request = self.client.client.get(&url);
 
-        // Add authentication headers
-        request = self.client.add_auth_headers(request);
+            // Add custom headers
+            for (key, value) in &self.headers {
+                request = request.header(key, value);
+            }
 
-        // Add custom headers
-        for (key, value) in self.headers {
-            request = request.header(key, value);
+            request
+        };
+
+        match &self.user_token {
+            Some(user_token) => {
+                self.client.refresh_developer_token().await?;
+                let response = self
+                    .client
+                    .add_auth_headers(build(), Some(user_token.expose()))
+                    .send()
+                    .await
+                    .map_err(AppleMusicError::Http)?;
+                self.client
+                    .handle_response(response, &self.path, true)
+                    .await
+            }
+            None => self.client.send(&self.path, build).await,
         }
-
-        let response = request.send().await.map_err(AppleMusicError::Http)?;
-        self.client.handle_response(response).await
     }
 
     /// Execute GET request and parse JSON response
@@ -256,7 +434,7 @@ impl<'a> RequestBuilder<'a> {
 
 impl HttpClient {
     /// Create a request builder
-    pub fn request(&self, path: impl Into<String>) -> RequestBuilder {
+    pub fn request(&self, path: impl Into<String>) -> RequestBuilder<'_> {
         RequestBuilder::new(self, path)
     }
 }
@@ -13,16 +13,18 @@
 //! ## Example
 //!
 //! ```rust,no_run
-//! use apple_music_api::{AppleMusicClient, ClientConfig};
+//! use apple_music_api::{config::MediaType, AppleMusicClient, ClientConfig};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let config = ClientConfig::new("your-developer-token")?;
-//!     let client = AppleMusicClient::new(config)?;
+//!     let config = ClientConfig::new("your-key-id", "your-team-id", "your-private-key")?;
+//!     let client = AppleMusicClient::new(config).await?;
 //!
 //!     // Search for songs
-//!     let results = client.search("Hello", &["songs"]).await?;
-//!     println!("Found {} songs", results.songs.len());
+//!     let results = client.search("Hello", &[MediaType::Songs]).await?;
+//!     if let Some(songs) = &results.results.songs {
+//!         println!("Found {} songs", songs.data.len());
+//!     }
 //!
 //!     Ok(())
 //! }
@@ -30,18 +32,31 @@
 
 pub mod auth;
 pub mod client;
+pub mod clock;
 pub mod config;
+pub mod credentials;
 pub mod error;
 pub mod http;
 pub mod models;
+pub mod profiles;
+pub mod secret;
+pub mod storefronts;
+#[cfg(feature = "user-token-server")]
+pub mod user_token_server;
 pub mod utils;
 
 // Re-export main types for convenience
 pub use auth::create_developer_token;
 pub use client::AppleMusicClient;
 pub use config::ClientConfig;
+pub use credentials::{CredentialStore, FileCredentialStore, MemoryCredentialStore};
 pub use error::AppleMusicError;
 pub use models::*;
+pub use secret::Secret;
+pub use storefronts::StorefrontRegistry;
+#[cfg(feature = "user-token-server")]
+pub use user_token_server::UserTokenServer;
+pub use utils::normalize_upc;
 // Re-export common types
 pub use reqwest;
 pub use serde_json;
@@ -1,8 +1,11 @@
 //! Data models for Apple Music catalog API responses
 
 use super::common::*;
+use super::search::Station;
+use crate::config::MediaType;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Song resource from the catalog
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,7 +32,8 @@ pub struct Song {
 }
 
 /// Song attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SongAttributes {
     /// The album name
     #[serde(rename = "albumName")]
@@ -110,6 +114,14 @@ pub struct SongAttributes {
     /// Whether the song can be edited
     #[serde(rename = "canEdit")]
     pub can_edit: Option<bool>,
+
+    /// The artist URL, returned with `extend=artistUrl`
+    #[serde(rename = "artistUrl")]
+    pub artist_url: Option<String>,
+
+    /// The audio variants, returned with `extend=audioVariants`
+    #[serde(rename = "audioVariants")]
+    pub audio_variants: Option<Vec<String>>,
 }
 
 /// Song relationships
@@ -126,6 +138,18 @@ pub struct SongRelationships {
     /// The genres relationship
     #[serde(rename = "genres")]
     pub genres: Option<Relationship<Genre>>,
+
+    /// The composers relationship
+    #[serde(rename = "composers")]
+    pub composers: Option<Relationship<Artist>>,
+
+    /// The music videos relationship
+    #[serde(rename = "music-videos")]
+    pub music_videos: Option<Relationship<MusicVideo>>,
+
+    /// The station relationship
+    #[serde(rename = "station")]
+    pub station: Option<Relationship<Station>>,
 }
 
 /// Preview information
@@ -158,10 +182,15 @@ pub struct Album {
     /// The album relationships
     #[serde(rename = "relationships")]
     pub relationships: Option<AlbumRelationships>,
+
+    /// The album views, by name
+    #[serde(rename = "views")]
+    pub views: Option<HashMap<String, View>>,
 }
 
 /// Album attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AlbumAttributes {
     /// The album name
     #[serde(rename = "name")]
@@ -226,10 +255,22 @@ pub struct AlbumAttributes {
     /// The play parameters
     #[serde(rename = "playParams")]
     pub play_params: Option<PlayParameters>,
+
+    /// The artist URL, returned with `extend=artistUrl`
+    #[serde(rename = "artistUrl")]
+    pub artist_url: Option<String>,
+
+    /// The audio variants, returned with `extend=audioVariants`
+    #[serde(rename = "audioVariants")]
+    pub audio_variants: Option<Vec<String>>,
+
+    /// The editorial artwork by kind, returned with `extend=editorialArtwork`
+    #[serde(rename = "editorialArtwork")]
+    pub editorial_artwork: Option<HashMap<String, Artwork>>,
 }
 
 /// Album relationships
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AlbumRelationships {
     /// The artists relationship
     #[serde(rename = "artists")]
@@ -242,6 +283,10 @@ pub struct AlbumRelationships {
     /// The tracks relationship
     #[serde(rename = "tracks")]
     pub tracks: Option<Relationship<Song>>,
+
+    /// The record labels relationship
+    #[serde(rename = "record-labels")]
+    pub record_labels: Option<Relationship<RecordLabel>>,
 }
 
 /// Artist resource from the catalog
@@ -266,10 +311,15 @@ pub struct Artist {
     /// The artist relationships
     #[serde(rename = "relationships")]
     pub relationships: Option<ArtistRelationships>,
+
+    /// The artist views, by name
+    #[serde(rename = "views")]
+    pub views: Option<HashMap<String, View>>,
 }
 
 /// Artist attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ArtistAttributes {
     /// The artist name
     #[serde(rename = "name")]
@@ -290,6 +340,22 @@ pub struct ArtistAttributes {
     /// The artwork
     #[serde(rename = "artwork")]
     pub artwork: Option<Artwork>,
+
+    /// Where the artist was born or formed, returned with `extend=bornOrFormed`
+    #[serde(rename = "bornOrFormed")]
+    pub born_or_formed: Option<String>,
+
+    /// The editorial artwork by kind, returned with `extend=editorialArtwork`
+    #[serde(rename = "editorialArtwork")]
+    pub editorial_artwork: Option<HashMap<String, Artwork>>,
+
+    /// Whether the artist is a group, returned with `extend=isGroup`
+    #[serde(rename = "isGroup")]
+    pub is_group: Option<bool>,
+
+    /// The artist's origin, returned with `extend=origin`
+    #[serde(rename = "origin")]
+    pub origin: Option<String>,
 }
 
 /// Artist relationships
@@ -310,6 +376,10 @@ pub struct ArtistRelationships {
     /// The playlists relationship
     #[serde(rename = "playlists")]
     pub playlists: Option<Relationship<Playlist>>,
+
+    /// The station relationship
+    #[serde(rename = "station")]
+    pub station: Option<Relationship<Station>>,
 }
 
 /// Music video resource
@@ -330,10 +400,19 @@ pub struct MusicVideo {
     /// The music video attributes
     #[serde(rename = "attributes")]
     pub attributes: MusicVideoAttributes,
+
+    /// The music video relationships
+    #[serde(rename = "relationships")]
+    pub relationships: Option<MusicVideoRelationships>,
+
+    /// The music video views, by name
+    #[serde(rename = "views")]
+    pub views: Option<HashMap<String, View>>,
 }
 
 /// Music video attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MusicVideoAttributes {
     /// The album name
     #[serde(rename = "albumName")]
@@ -402,6 +481,30 @@ pub struct MusicVideoAttributes {
     /// The video subtype
     #[serde(rename = "videoSubType")]
     pub video_sub_type: Option<String>,
+
+    /// The artist URL, returned with `extend=artistUrl`
+    #[serde(rename = "artistUrl")]
+    pub artist_url: Option<String>,
+}
+
+/// Music video relationships
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MusicVideoRelationships {
+    /// The albums containing the music video
+    #[serde(rename = "albums")]
+    pub albums: Option<Relationship<Album>>,
+
+    /// The artists of the music video
+    #[serde(rename = "artists")]
+    pub artists: Option<Relationship<Artist>>,
+
+    /// The genres of the music video
+    #[serde(rename = "genres")]
+    pub genres: Option<Relationship<Genre>>,
+
+    /// The songs matching the music video
+    #[serde(rename = "songs")]
+    pub songs: Option<Relationship<Song>>,
 }
 
 /// Playlist resource
@@ -426,10 +529,15 @@ pub struct Playlist {
     /// The playlist relationships
     #[serde(rename = "relationships")]
     pub relationships: Option<PlaylistRelationships>,
+
+    /// The playlist views, by name
+    #[serde(rename = "views")]
+    pub views: Option<HashMap<String, View>>,
 }
 
 /// Playlist attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PlaylistAttributes {
     /// The curator name
     #[serde(rename = "curatorName")]
@@ -470,10 +578,18 @@ pub struct PlaylistAttributes {
     /// Whether the playlist has a catalog
     #[serde(rename = "hasCatalog")]
     pub has_catalog: Option<bool>,
+
+    /// The editorial artwork by kind, returned with `extend=editorialArtwork`
+    #[serde(rename = "editorialArtwork")]
+    pub editorial_artwork: Option<HashMap<String, Artwork>>,
+
+    /// The types of tracks in the playlist, returned with `extend=trackTypes`
+    #[serde(rename = "trackTypes")]
+    pub track_types: Option<Vec<String>>,
 }
 
 /// Playlist relationships
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PlaylistRelationships {
     /// The curator relationship
     #[serde(rename = "curator")]
@@ -484,15 +600,6 @@ pub struct PlaylistRelationships {
     pub tracks: Option<Relationship<Song>>,
 }
 
-impl Default for PlaylistRelationships {
-    fn default() -> Self {
-        PlaylistRelationships {
-            curator: None,
-            tracks: None,
-        }
-    }
-}
-
 /// Curator resource
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Curator {
@@ -511,10 +618,15 @@ pub struct Curator {
     /// The curator attributes
     #[serde(rename = "attributes")]
     pub attributes: CuratorAttributes,
+
+    /// The curator relationships
+    #[serde(rename = "relationships")]
+    pub relationships: Option<CuratorRelationships>,
 }
 
 /// Curator attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CuratorAttributes {
     /// The artwork
     #[serde(rename = "artwork")]
@@ -531,4 +643,217 @@ pub struct CuratorAttributes {
     /// The URL
     #[serde(rename = "url")]
     pub url: String,
+
+    /// The kind of Apple curator, e.g. `Editorial` or `Show`
+    #[serde(rename = "kind")]
+    pub kind: Option<String>,
+
+    /// The short name of an Apple curator
+    #[serde(rename = "shortName")]
+    pub short_name: Option<String>,
+
+    /// The host name of an Apple curator show
+    #[serde(rename = "showHostName")]
+    pub show_host_name: Option<String>,
+}
+
+/// Curator relationships
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CuratorRelationships {
+    /// The playlists of the curator
+    #[serde(rename = "playlists")]
+    pub playlists: Option<Relationship<Playlist>>,
+}
+
+/// Activity resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    /// The activity ID
+    #[serde(rename = "id")]
+    pub id: String,
+
+    /// The resource type
+    #[serde(rename = "type")]
+    pub resource_type: String,
+
+    /// The activity href
+    #[serde(rename = "href")]
+    pub href: Option<String>,
+
+    /// The activity attributes
+    #[serde(rename = "attributes")]
+    pub attributes: ActivityAttributes,
+
+    /// The activity relationships
+    #[serde(rename = "relationships")]
+    pub relationships: Option<ActivityRelationships>,
+}
+
+/// Activity attributes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActivityAttributes {
+    /// The artwork
+    #[serde(rename = "artwork")]
+    pub artwork: Option<Artwork>,
+
+    /// The editorial notes
+    #[serde(rename = "editorialNotes")]
+    pub editorial_notes: Option<EditorialNotes>,
+
+    /// The activity name
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// The URL
+    #[serde(rename = "url")]
+    pub url: String,
+}
+
+/// Activity relationships
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityRelationships {
+    /// The playlists of the activity
+    #[serde(rename = "playlists")]
+    pub playlists: Option<Relationship<Playlist>>,
+}
+
+/// Record label resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordLabel {
+    /// The record label ID
+    #[serde(rename = "id")]
+    pub id: String,
+
+    /// The resource type
+    #[serde(rename = "type")]
+    pub resource_type: String,
+
+    /// The record label href
+    #[serde(rename = "href")]
+    pub href: Option<String>,
+
+    /// The record label attributes
+    #[serde(rename = "attributes")]
+    pub attributes: RecordLabelAttributes,
+
+    /// The record label views, by name
+    #[serde(rename = "views")]
+    pub views: Option<HashMap<String, View>>,
+}
+
+/// Record label attributes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordLabelAttributes {
+    /// The artwork
+    #[serde(rename = "artwork")]
+    pub artwork: Option<Artwork>,
+
+    /// The description
+    #[serde(rename = "description")]
+    pub description: Option<EditorialNotes>,
+
+    /// The record label name
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// The URL
+    #[serde(rename = "url")]
+    pub url: String,
+}
+
+/// Catalog resources of several types fetched in a single request
+#[derive(Debug, Clone, Default)]
+pub struct CatalogResources {
+    /// The songs found
+    pub songs: Vec<Song>,
+
+    /// The albums found
+    pub albums: Vec<Album>,
+
+    /// The artists found
+    pub artists: Vec<Artist>,
+
+    /// The playlists found
+    pub playlists: Vec<Playlist>,
+
+    /// The music videos found
+    pub music_videos: Vec<MusicVideo>,
+
+    /// The stations found
+    pub stations: Vec<Station>,
+
+    /// The Apple curators found
+    pub apple_curators: Vec<Curator>,
+
+    /// The curators found
+    pub curators: Vec<Curator>,
+
+    /// Requested IDs that were not returned, by type
+    pub missing: HashMap<MediaType, Vec<String>>,
+}
+
+impl CatalogResources {
+    /// Add a resource to the group matching its `type`
+    ///
+    /// Resources of types that are not grouped are ignored.
+    pub(crate) fn insert(&mut self, resource: serde_json::Value) -> serde_json::Result<()> {
+        match resource.get("type").and_then(|kind| kind.as_str()) {
+            Some("songs") => self.songs.push(serde_json::from_value(resource)?),
+            Some("albums") => self.albums.push(serde_json::from_value(resource)?),
+            Some("artists") => self.artists.push(serde_json::from_value(resource)?),
+            Some("playlists") => self.playlists.push(serde_json::from_value(resource)?),
+            Some("music-videos") => self.music_videos.push(serde_json::from_value(resource)?),
+            Some("stations") => self.stations.push(serde_json::from_value(resource)?),
+            Some("apple-curators") => self.apple_curators.push(serde_json::from_value(resource)?),
+            Some("curators") => self.curators.push(serde_json::from_value(resource)?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Get the IDs of the resources found for a media type
+    pub fn ids(&self, media_type: MediaType) -> Vec<&str> {
+        match media_type {
+            MediaType::Songs => self.songs.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::Albums => self.albums.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::Artists => self.artists.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::Playlists => self.playlists.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::MusicVideos => self.music_videos.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::Stations => self.stations.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::AppleCurators => self.apple_curators.iter().map(|r| r.id.as_str()).collect(),
+            MediaType::Curators => self.curators.iter().map(|r| r.id.as_str()).collect(),
+        }
+    }
+
+    /// Check if every requested resource was found
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Catalog resources matched to their equivalents in another storefront
+#[derive(Debug, Clone)]
+pub struct Equivalents<T> {
+    /// The storefront the equivalents belong to
+    pub storefront: String,
+
+    /// The equivalent resource for each matched ID
+    pub matches: HashMap<String, T>,
+
+    /// Requested IDs with no equivalent in the storefront
+    pub unmatched: Vec<String>,
+}
+
+impl<T> Equivalents<T> {
+    /// Get the equivalent resource for a requested ID
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.matches.get(id)
+    }
+
+    /// Check if every requested ID has an equivalent
+    pub fn is_complete(&self) -> bool {
+        self.unmatched.is_empty()
+    }
 }
@@ -0,0 +1,111 @@
+//! Data models for Apple Music chart responses
+
+use super::catalog::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Charts response from the Apple Music API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartsResponse {
+    /// Charts organized by media type
+    #[serde(rename = "results")]
+    pub results: ChartResults,
+
+    /// Meta information about the charts
+    #[serde(rename = "meta")]
+    pub meta: Option<ChartsMeta>,
+}
+
+/// Charts organized by media type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChartResults {
+    /// Song charts
+    #[serde(rename = "songs")]
+    pub songs: Option<Vec<Chart<Song>>>,
+
+    /// Album charts
+    #[serde(rename = "albums")]
+    pub albums: Option<Vec<Chart<Album>>>,
+
+    /// Playlist charts
+    #[serde(rename = "playlists")]
+    pub playlists: Option<Vec<Chart<Playlist>>>,
+
+    /// Music video charts
+    #[serde(rename = "music-videos")]
+    pub music_videos: Option<Vec<Chart<MusicVideo>>>,
+}
+
+/// A single chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chart<T> {
+    /// The chart identifier, e.g. `most-played`
+    #[serde(rename = "chart")]
+    pub chart: String,
+
+    /// The localized chart name
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// The identifier used in [`ChartsResultsMeta::order`]
+    #[serde(rename = "orderId")]
+    pub order_id: Option<String>,
+
+    /// The chart entries, in rank order
+    #[serde(rename = "data")]
+    pub data: Vec<T>,
+
+    /// The chart href
+    #[serde(rename = "href")]
+    pub href: Option<String>,
+
+    /// The next URL for pagination
+    #[serde(rename = "next")]
+    pub next: Option<String>,
+}
+
+/// Charts meta information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartsMeta {
+    /// Chart results information
+    #[serde(rename = "results")]
+    pub results: Option<ChartsResultsMeta>,
+}
+
+/// Chart results meta information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartsResultsMeta {
+    /// Order in which to display the charts, by [`Chart::order_id`]
+    #[serde(rename = "order")]
+    pub order: Vec<String>,
+}
+
+/// Resource types that appear in charts
+pub trait ChartItem: DeserializeOwned + Sized {
+    /// Take the charts of this type out of a response
+    fn take_charts(results: &mut ChartResults) -> Option<Vec<Chart<Self>>>;
+}
+
+impl ChartItem for Song {
+    fn take_charts(results: &mut ChartResults) -> Option<Vec<Chart<Self>>> {
+        results.songs.take()
+    }
+}
+
+impl ChartItem for Album {
+    fn take_charts(results: &mut ChartResults) -> Option<Vec<Chart<Self>>> {
+        results.albums.take()
+    }
+}
+
+impl ChartItem for Playlist {
+    fn take_charts(results: &mut ChartResults) -> Option<Vec<Chart<Self>>> {
+        results.playlists.take()
+    }
+}
+
+impl ChartItem for MusicVideo {
+    fn take_charts(results: &mut ChartResults) -> Option<Vec<Chart<Self>>> {
+        results.music_videos.take()
+    }
+}
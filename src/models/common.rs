@@ -54,7 +54,7 @@ pub struct CommonAttributes {
 }
 
 /// Artwork information for resources
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Artwork {
     /// The average background color of the artwork
     #[serde(rename = "bgColor")]
@@ -189,6 +189,45 @@ impl<T> Default for Relationship<T> {
     }
 }
 
+/// A view of a resource, requested with the `views` query parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct View {
+    /// The resources in the view, of the type the view holds
+    #[serde(rename = "data", default)]
+    pub data: Vec<serde_json::Value>,
+
+    /// The URL for the view
+    #[serde(rename = "href")]
+    pub href: Option<String>,
+
+    /// The next URL for pagination
+    #[serde(rename = "next")]
+    pub next: Option<String>,
+
+    /// The view attributes
+    #[serde(rename = "attributes")]
+    pub attributes: Option<ViewAttributes>,
+}
+
+impl View {
+    /// Deserialize the resources in the view, e.g. as `Vec<Song>`
+    pub fn items<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<Vec<T>> {
+        self.data
+            .iter()
+            .cloned()
+            .map(serde_json::from_value)
+            .collect()
+    }
+}
+
+/// View attributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewAttributes {
+    /// The localized title of the view
+    #[serde(rename = "title")]
+    pub title: Option<String>,
+}
+
 /// Pagination information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginationMeta {
@@ -1,5 +1,6 @@
 //! Data models for Apple Music library API responses
 
+use super::catalog::{Album, Artist, Playlist, Song};
 use super::common::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -98,6 +99,10 @@ pub struct LibrarySongRelationships {
     /// The artists relationship
     #[serde(rename = "artists")]
     pub artists: Option<Relationship<LibraryArtist>>,
+
+    /// The matching catalog song, returned with `include=catalog`
+    #[serde(rename = "catalog")]
+    pub catalog: Option<Relationship<Song>>,
 }
 
 /// Library album resource
@@ -174,6 +179,10 @@ pub struct LibraryAlbumRelationships {
     /// The tracks relationship
     #[serde(rename = "tracks")]
     pub tracks: Option<Relationship<LibrarySong>>,
+
+    /// The matching catalog album, returned with `include=catalog`
+    #[serde(rename = "catalog")]
+    pub catalog: Option<Relationship<Album>>,
 }
 
 /// Library artist resource
@@ -226,6 +235,10 @@ pub struct LibraryArtistRelationships {
     /// The albums relationship
     #[serde(rename = "albums")]
     pub albums: Option<Relationship<LibraryAlbum>>,
+
+    /// The matching catalog artist, returned with `include=catalog`
+    #[serde(rename = "catalog")]
+    pub catalog: Option<Relationship<Artist>>,
 }
 
 /// Library playlist resource
@@ -286,6 +299,10 @@ pub struct LibraryPlaylistRelationships {
     /// The tracks relationship
     #[serde(rename = "tracks")]
     pub tracks: Option<Relationship<LibrarySong>>,
+
+    /// The matching catalog playlist, returned with `include=catalog`
+    #[serde(rename = "catalog")]
+    pub catalog: Option<Relationship<Playlist>>,
 }
 
 /// Library music video resource
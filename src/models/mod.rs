@@ -1,6 +1,7 @@
 //! Data models for Apple Music API responses
 
 pub mod catalog;
+pub mod charts;
 pub mod common;
 pub mod library;
 pub mod search;
@@ -1,7 +1,7 @@
 //! Data models for Apple Music search API responses
 
 use super::catalog::*;
-use super::common::{Artwork, EditorialNotes};
+use super::common::{Artwork, EditorialNotes, PlayParameters, Relationship};
 use serde::{Deserialize, Serialize};
 
 /// Search response from the Apple Music API
@@ -109,7 +109,8 @@ pub struct Station {
 }
 
 /// Station attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StationAttributes {
     /// The artwork
     #[serde(rename = "artwork")]
@@ -142,6 +143,62 @@ pub struct StationAttributes {
     /// The media kind
     #[serde(rename = "mediaKind")]
     pub media_kind: String,
+
+    /// The content rating
+    #[serde(rename = "contentRating")]
+    pub content_rating: Option<String>,
+
+    /// The play parameters
+    #[serde(rename = "playParams")]
+    pub play_params: Option<PlayParameters>,
+
+    /// Whether playing the station requires a subscription
+    #[serde(rename = "requiresSubscription")]
+    pub requires_subscription: Option<bool>,
+
+    /// The name of the station provider
+    #[serde(rename = "stationProviderName")]
+    pub station_provider_name: Option<String>,
+}
+
+/// Station genre resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationGenre {
+    /// The station genre ID
+    #[serde(rename = "id")]
+    pub id: String,
+
+    /// The resource type
+    #[serde(rename = "type")]
+    pub resource_type: String,
+
+    /// The station genre href
+    #[serde(rename = "href")]
+    pub href: Option<String>,
+
+    /// The station genre attributes
+    #[serde(rename = "attributes")]
+    pub attributes: Option<StationGenreAttributes>,
+
+    /// The station genre relationships
+    #[serde(rename = "relationships")]
+    pub relationships: Option<StationGenreRelationships>,
+}
+
+/// Station genre attributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationGenreAttributes {
+    /// The station genre name
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+/// Station genre relationships
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StationGenreRelationships {
+    /// The stations of the genre
+    #[serde(rename = "stations")]
+    pub stations: Option<Relationship<Station>>,
 }
 
 /// Hints response for search suggestions
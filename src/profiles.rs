@@ -0,0 +1,194 @@
+//! Named configuration profiles loaded from a TOML file
+//!
+//! ```toml
+//! # Used when no profile is selected explicitly
+//! default_profile = "staging"
+//!
+//! [profiles.default]
+//! team_id = "ABCDE12345"
+//! storefront = "us"
+//! timeout = 30
+//!
+//! [profiles.staging]
+//! key_id = "STAGING123"
+//! private_key_path = "keys/staging.p8"
+//! base_url = "http://localhost:8080"
+//!
+//! [profiles.production]
+//! key_id = "PROD123456"
+//! private_key_path = "keys/production.p8"
+//! storefront = "ca"
+//! language = "fr-CA"
+//! ```
+//!
+//! Every profile inherits the fields of `[profiles.default]`, and the
+//! `APPLE_MUSIC_*` environment variables read by
+//! [`ClientConfig::from_env`] override both. The profile is chosen by name,
+//! then by `APPLE_MUSIC_PROFILE`, then by `default_profile`, and falls back
+//! to `default`. Relative key paths are resolved against the file's
+//! directory.
+//!
+//! Setting `developer_token`, `private_key` or `private_key_path` replaces
+//! all three inherited ones, so a profile or variable can switch between a
+//! pre-generated token, an inline key and a key file.
+
+use crate::config::{env_var_name, ClientConfig, Setting, SETTINGS};
+use crate::error::{AppleMusicError, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Environment variable selecting the profile
+pub const ENV_PROFILE: &str = "APPLE_MUSIC_PROFILE";
+
+/// Name of the profile every other profile inherits from
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Settings choosing the developer token or the private key to sign one,
+/// which are replaced together
+const CREDENTIAL_SETTINGS: [&str; 3] = ["developer_token", "private_key", "private_key_path"];
+
+/// A profile file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    /// Profile used when none is selected explicitly
+    default_profile: Option<String>,
+
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// A named profile, with the fields of [`ClientConfig`] that can be configured
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    developer_token: Option<String>,
+    team_id: Option<String>,
+    key_id: Option<String>,
+    private_key: Option<String>,
+    private_key_path: Option<String>,
+    storefront: Option<String>,
+    language: Option<String>,
+    base_url: Option<String>,
+    timeout: Option<u64>,
+    max_retries: Option<u32>,
+    proxy: Option<String>,
+    user_token: Option<String>,
+}
+
+impl Profile {
+    /// Get the fields set in this profile as settings
+    fn settings(&self, name: &str) -> Vec<(&'static str, Setting)> {
+        let fields = [
+            ("developer_token", self.developer_token.clone()),
+            ("team_id", self.team_id.clone()),
+            ("key_id", self.key_id.clone()),
+            ("private_key", self.private_key.clone()),
+            ("private_key_path", self.private_key_path.clone()),
+            ("storefront", self.storefront.clone()),
+            ("language", self.language.clone()),
+            ("base_url", self.base_url.clone()),
+            ("timeout", self.timeout.map(|timeout| timeout.to_string())),
+            (
+                "max_retries",
+                self.max_retries.map(|retries| retries.to_string()),
+            ),
+            ("proxy", self.proxy.clone()),
+            ("user_token", self.user_token.clone()),
+        ];
+        fields
+            .into_iter()
+            .filter_map(|(field, value)| {
+                let origin = format!("profile {:?}, field {:?}", name, field);
+                Some((field, Setting::new(value?, origin)))
+            })
+            .collect()
+    }
+}
+
+/// Load a profile from the contents of a profile file
+pub(crate) fn load_profile(
+    contents: &str,
+    source: &str,
+    profile: Option<&str>,
+    env_vars: &HashMap<String, String>,
+    base_dir: Option<&Path>,
+) -> Result<ClientConfig> {
+    let file: ProfileFile = toml::from_str(contents)
+        .map_err(|e| AppleMusicError::config(format!("{}: {}", source, e)))?;
+
+    let name = profile
+        .map(str::to_string)
+        .or_else(|| env_vars.get(ENV_PROFILE).cloned())
+        .or(file.default_profile)
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+    let Some(selected) = file.profiles.get(&name) else {
+        let available = file
+            .profiles
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(AppleMusicError::config(format!(
+            "{}: profile {:?} not found (available: {})",
+            source, name, available
+        )));
+    };
+
+    // Inherit from the default profile, then apply the selected one
+    let mut settings = HashMap::new();
+    if let Some(default) = file.profiles.get(DEFAULT_PROFILE) {
+        merge(&mut settings, default.settings(DEFAULT_PROFILE));
+    }
+    if name != DEFAULT_PROFILE {
+        merge(&mut settings, selected.settings(&name));
+    }
+
+    // Environment variables take precedence over the file
+    merge(
+        &mut settings,
+        SETTINGS
+            .iter()
+            .filter_map(|&(field, variable)| {
+                let value = env_vars.get(variable)?;
+                Some((field, Setting::new(value.clone(), variable)))
+            })
+            .collect(),
+    );
+
+    let describe = |field: &str| {
+        format!(
+            "profile {:?}, field {:?} (or {})",
+            name,
+            field,
+            env_var_name(field)
+        )
+    };
+    let mut config = ClientConfig::from_settings(
+        &settings,
+        &describe,
+        &format!("Invalid configuration for profile {:?} in {}", name, source),
+        base_dir,
+    )?;
+    config.profile = Some(name);
+    Ok(config)
+}
+
+/// Apply the settings of a higher-priority source
+///
+/// A source setting a developer token or either private key field replaces
+/// all three, so an inline key and a key path from different sources never
+/// conflict, and an inherited developer token never shadows a profile's key.
+fn merge(settings: &mut HashMap<&'static str, Setting>, source: Vec<(&'static str, Setting)>) {
+    if source
+        .iter()
+        .any(|(field, _)| CREDENTIAL_SETTINGS.contains(field))
+    {
+        for field in CREDENTIAL_SETTINGS {
+            settings.remove(field);
+        }
+    }
+    settings.extend(source);
+}
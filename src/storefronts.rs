@@ -0,0 +1,333 @@
+//! Registry of known Apple Music storefronts
+//!
+//! A snapshot of the storefronts is bundled with the crate so configurations
+//! can be checked offline. Use
+//! [`AppleMusicClient::refresh_storefront_registry`](crate::AppleMusicClient::refresh_storefront_registry)
+//! to build an up-to-date registry from the API.
+
+use crate::error::{AppleMusicError, Result};
+use crate::models::common::Storefront;
+use std::collections::BTreeMap;
+
+/// Storefronts known when this version of the crate was released
+const BUNDLED: &[(&str, &str, &str)] = &[
+    ("ae", "United Arab Emirates", "en-GB"),
+    ("ag", "Antigua and Barbuda", "en-GB"),
+    ("ai", "Anguilla", "en-GB"),
+    ("al", "Albania", "en-GB"),
+    ("am", "Armenia", "en-GB"),
+    ("ao", "Angola", "en-GB"),
+    ("ar", "Argentina", "es-MX"),
+    ("at", "Austria", "de-DE"),
+    ("au", "Australia", "en-AU"),
+    ("az", "Azerbaijan", "en-GB"),
+    ("ba", "Bosnia and Herzegovina", "en-GB"),
+    ("bb", "Barbados", "en-GB"),
+    ("be", "Belgium", "en-GB"),
+    ("bf", "Burkina Faso", "fr-FR"),
+    ("bg", "Bulgaria", "en-GB"),
+    ("bh", "Bahrain", "en-GB"),
+    ("bj", "Benin", "fr-FR"),
+    ("bm", "Bermuda", "en-GB"),
+    ("bn", "Brunei Darussalam", "en-GB"),
+    ("bo", "Bolivia", "es-MX"),
+    ("br", "Brazil", "pt-BR"),
+    ("bs", "Bahamas", "en-GB"),
+    ("bt", "Bhutan", "en-GB"),
+    ("bw", "Botswana", "en-GB"),
+    ("by", "Belarus", "en-GB"),
+    ("bz", "Belize", "en-GB"),
+    ("ca", "Canada", "en-CA"),
+    ("cd", "Democratic Republic of the Congo", "fr-FR"),
+    ("cg", "Republic of the Congo", "fr-FR"),
+    ("ch", "Switzerland", "de-CH"),
+    ("ci", "Côte d'Ivoire", "fr-FR"),
+    ("cl", "Chile", "es-MX"),
+    ("cm", "Cameroon", "fr-FR"),
+    ("cn", "China mainland", "zh-Hans-CN"),
+    ("co", "Colombia", "es-MX"),
+    ("cr", "Costa Rica", "es-MX"),
+    ("cv", "Cape Verde", "en-GB"),
+    ("cy", "Cyprus", "en-GB"),
+    ("cz", "Czechia", "en-GB"),
+    ("de", "Germany", "de-DE"),
+    ("dk", "Denmark", "en-GB"),
+    ("dm", "Dominica", "en-GB"),
+    ("do", "Dominican Republic", "es-MX"),
+    ("dz", "Algeria", "en-GB"),
+    ("ec", "Ecuador", "es-MX"),
+    ("ee", "Estonia", "en-GB"),
+    ("eg", "Egypt", "en-GB"),
+    ("es", "Spain", "es-ES"),
+    ("fi", "Finland", "en-GB"),
+    ("fj", "Fiji", "en-GB"),
+    ("fm", "Micronesia", "en-GB"),
+    ("fr", "France", "fr-FR"),
+    ("ga", "Gabon", "fr-FR"),
+    ("gb", "United Kingdom", "en-GB"),
+    ("gd", "Grenada", "en-GB"),
+    ("ge", "Georgia", "en-GB"),
+    ("gh", "Ghana", "en-GB"),
+    ("gm", "Gambia", "en-GB"),
+    ("gr", "Greece", "en-GB"),
+    ("gt", "Guatemala", "es-MX"),
+    ("gw", "Guinea-Bissau", "en-GB"),
+    ("gy", "Guyana", "en-GB"),
+    ("hk", "Hong Kong", "zh-Hant-HK"),
+    ("hn", "Honduras", "es-MX"),
+    ("hr", "Croatia", "en-GB"),
+    ("hu", "Hungary", "en-GB"),
+    ("id", "Indonesia", "en-GB"),
+    ("ie", "Ireland", "en-GB"),
+    ("il", "Israel", "en-GB"),
+    ("in", "India", "en-GB"),
+    ("iq", "Iraq", "en-GB"),
+    ("is", "Iceland", "en-GB"),
+    ("it", "Italy", "it-IT"),
+    ("jm", "Jamaica", "en-GB"),
+    ("jo", "Jordan", "en-GB"),
+    ("jp", "Japan", "ja"),
+    ("ke", "Kenya", "en-GB"),
+    ("kg", "Kyrgyzstan", "en-GB"),
+    ("kh", "Cambodia", "en-GB"),
+    ("kn", "St. Kitts and Nevis", "en-GB"),
+    ("kr", "Republic of Korea", "ko"),
+    ("kw", "Kuwait", "en-GB"),
+    ("ky", "Cayman Islands", "en-GB"),
+    ("kz", "Kazakhstan", "en-GB"),
+    ("la", "Lao People's Democratic Republic", "en-GB"),
+    ("lb", "Lebanon", "en-GB"),
+    ("lc", "St. Lucia", "en-GB"),
+    ("lk", "Sri Lanka", "en-GB"),
+    ("lr", "Liberia", "en-GB"),
+    ("lt", "Lithuania", "en-GB"),
+    ("lu", "Luxembourg", "en-GB"),
+    ("lv", "Latvia", "en-GB"),
+    ("ly", "Libya", "en-GB"),
+    ("ma", "Morocco", "fr-FR"),
+    ("md", "Moldova", "en-GB"),
+    ("me", "Montenegro", "en-GB"),
+    ("mg", "Madagascar", "fr-FR"),
+    ("mk", "North Macedonia", "en-GB"),
+    ("ml", "Mali", "fr-FR"),
+    ("mm", "Myanmar", "en-GB"),
+    ("mn", "Mongolia", "en-GB"),
+    ("mo", "Macau", "zh-Hant-HK"),
+    ("mr", "Mauritania", "fr-FR"),
+    ("ms", "Montserrat", "en-GB"),
+    ("mt", "Malta", "en-GB"),
+    ("mu", "Mauritius", "en-GB"),
+    ("mv", "Maldives", "en-GB"),
+    ("mw", "Malawi", "en-GB"),
+    ("mx", "Mexico", "es-MX"),
+    ("my", "Malaysia", "en-GB"),
+    ("mz", "Mozambique", "en-GB"),
+    ("na", "Namibia", "en-GB"),
+    ("ne", "Niger", "fr-FR"),
+    ("ng", "Nigeria", "en-GB"),
+    ("ni", "Nicaragua", "es-MX"),
+    ("nl", "Netherlands", "nl"),
+    ("no", "Norway", "nb"),
+    ("np", "Nepal", "en-GB"),
+    ("nz", "New Zealand", "en-AU"),
+    ("om", "Oman", "en-GB"),
+    ("pa", "Panama", "es-MX"),
+    ("pe", "Peru", "es-MX"),
+    ("pg", "Papua New Guinea", "en-GB"),
+    ("ph", "Philippines", "en-GB"),
+    ("pk", "Pakistan", "en-GB"),
+    ("pl", "Poland", "en-GB"),
+    ("pt", "Portugal", "pt-PT"),
+    ("pw", "Palau", "en-GB"),
+    ("py", "Paraguay", "es-MX"),
+    ("qa", "Qatar", "en-GB"),
+    ("ro", "Romania", "en-GB"),
+    ("rs", "Serbia", "en-GB"),
+    ("ru", "Russia", "ru"),
+    ("rw", "Rwanda", "en-GB"),
+    ("sa", "Saudi Arabia", "en-GB"),
+    ("sb", "Solomon Islands", "en-GB"),
+    ("sc", "Seychelles", "en-GB"),
+    ("se", "Sweden", "sv"),
+    ("sg", "Singapore", "en-GB"),
+    ("si", "Slovenia", "en-GB"),
+    ("sk", "Slovakia", "en-GB"),
+    ("sl", "Sierra Leone", "en-GB"),
+    ("sn", "Senegal", "fr-FR"),
+    ("sr", "Suriname", "en-GB"),
+    ("sv", "El Salvador", "es-MX"),
+    ("sz", "Eswatini", "en-GB"),
+    ("tc", "Turks and Caicos Islands", "en-GB"),
+    ("td", "Chad", "fr-FR"),
+    ("th", "Thailand", "en-GB"),
+    ("tj", "Tajikistan", "en-GB"),
+    ("tm", "Turkmenistan", "en-GB"),
+    ("tn", "Tunisia", "fr-FR"),
+    ("to", "Tonga", "en-GB"),
+    ("tr", "Türkiye", "en-GB"),
+    ("tt", "Trinidad and Tobago", "en-GB"),
+    ("tw", "Taiwan", "zh-Hant-TW"),
+    ("tz", "Tanzania", "en-GB"),
+    ("ua", "Ukraine", "en-GB"),
+    ("ug", "Uganda", "en-GB"),
+    ("us", "United States", "en-US"),
+    ("uy", "Uruguay", "es-MX"),
+    ("uz", "Uzbekistan", "en-GB"),
+    ("vc", "St. Vincent and the Grenadines", "en-GB"),
+    ("ve", "Venezuela", "es-MX"),
+    ("vg", "British Virgin Islands", "en-GB"),
+    ("vn", "Vietnam", "en-GB"),
+    ("vu", "Vanuatu", "en-GB"),
+    ("xk", "Kosovo", "en-GB"),
+    ("ye", "Yemen", "en-GB"),
+    ("za", "South Africa", "en-GB"),
+    ("zm", "Zambia", "en-GB"),
+    ("zw", "Zimbabwe", "en-GB"),
+];
+
+/// A known storefront
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorefrontInfo {
+    /// The storefront ID, e.g. `us`
+    pub id: String,
+
+    /// The storefront name
+    pub name: String,
+
+    /// The default language tag
+    pub default_language: String,
+}
+
+/// Set of valid storefronts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorefrontRegistry {
+    storefronts: BTreeMap<String, StorefrontInfo>,
+}
+
+impl StorefrontRegistry {
+    /// Create a registry from the bundled storefront list
+    pub fn bundled() -> Self {
+        Self {
+            storefronts: BUNDLED
+                .iter()
+                .map(|&(id, name, default_language)| {
+                    let info = StorefrontInfo {
+                        id: id.to_string(),
+                        name: name.to_string(),
+                        default_language: default_language.to_string(),
+                    };
+                    (info.id.clone(), info)
+                })
+                .collect(),
+        }
+    }
+
+    /// Create a registry from storefronts returned by the API
+    pub fn from_storefronts(storefronts: &[Storefront]) -> Self {
+        Self {
+            storefronts: storefronts
+                .iter()
+                .map(|storefront| {
+                    let id = storefront.id.to_lowercase();
+                    let (name, default_language) = match &storefront.attributes {
+                        Some(attributes) => (
+                            attributes.name.clone(),
+                            attributes.default_language_tag.clone(),
+                        ),
+                        None => (id.clone(), String::new()),
+                    };
+                    let info = StorefrontInfo {
+                        id: id.clone(),
+                        name,
+                        default_language,
+                    };
+                    (id, info)
+                })
+                .collect(),
+        }
+    }
+
+    /// Get a storefront by ID
+    pub fn get(&self, id: &str) -> Option<&StorefrontInfo> {
+        self.storefronts.get(&id.trim().to_lowercase())
+    }
+
+    /// Check if a storefront ID is known
+    pub fn contains(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Iterate over the storefronts in ID order
+    pub fn iter(&self) -> impl Iterator<Item = &StorefrontInfo> {
+        self.storefronts.values()
+    }
+
+    /// Get the number of storefronts
+    pub fn len(&self) -> usize {
+        self.storefronts.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.storefronts.is_empty()
+    }
+
+    /// Find the known storefront closest to a misspelled ID or name
+    ///
+    /// Longer inputs are compared with storefront names, so `"germny"`
+    /// suggests `de`.
+    pub fn suggest(&self, input: &str) -> Option<&StorefrontInfo> {
+        let input = input.trim().to_lowercase();
+        if input.is_empty() {
+            return None;
+        }
+
+        if input.len() > 3 {
+            return self
+                .iter()
+                .map(|info| {
+                    let name = info.name.to_lowercase();
+                    (strsim::normalized_levenshtein(&input, &name), info)
+                })
+                .filter(|(similarity, _)| *similarity >= 0.6)
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, info)| info);
+        }
+
+        // Prefer IDs sharing the first letter, so `uss` suggests `us`
+        self.iter()
+            .map(|info| {
+                let distance = strsim::levenshtein(&input, &info.id);
+                let other_start = info.id.chars().next() != input.chars().next();
+                ((distance, other_start), info)
+            })
+            .filter(|((distance, _), _)| *distance <= 1)
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, info)| info)
+    }
+
+    /// Validate a storefront ID, returning it in lowercase
+    ///
+    /// The error suggests the closest known storefront.
+    pub fn validate(&self, id: &str) -> Result<String> {
+        let storefront = id.trim().to_lowercase();
+        if self.storefronts.contains_key(&storefront) {
+            return Ok(storefront);
+        }
+
+        let mut message = match crate::utils::parse_storefront(id) {
+            Err(AppleMusicError::InvalidRequest(message)) => message,
+            _ => format!("Unknown storefront {:?}", storefront),
+        };
+        if let Some(info) = self.suggest(id) {
+            message.push_str(&format!("; did you mean {:?} ({})?", info.id, info.name));
+        }
+        Err(AppleMusicError::invalid_request(message))
+    }
+}
+
+impl Default for StorefrontRegistry {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
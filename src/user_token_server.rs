@@ -0,0 +1,332 @@
+//! Local user token acquisition through MusicKit JS
+//!
+//! Apple only hands out a `Music-User-Token` to MusicKit JS (or the native
+//! MusicKit frameworks) after the user signs in. This module starts a small
+//! HTTP server on the loopback interface that serves a page configuring
+//! MusicKit JS with the developer token. Once the user authorizes, the page
+//! posts the user token back to the server, which returns it to the caller.
+//! The page also echoes a random state generated for each server, so
+//! callbacks that did not come from the page are rejected.
+
+use crate::error::{AppleMusicError, Result};
+use crate::secret::Secret;
+use serde::Deserialize;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// Path the authorization page posts the user token to
+pub const CALLBACK_PATH: &str = "/callback";
+
+/// Maximum size of an incoming request (headers and body)
+const MAX_REQUEST_SIZE: usize = 64 * 1024;
+
+/// Time a connection has to send its complete request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Body posted by the authorization page to the callback route
+#[derive(Debug, Deserialize)]
+struct CallbackBody {
+    /// The authorized user token
+    #[serde(rename = "musicUserToken")]
+    music_user_token: Secret,
+
+    /// The state embedded in the authorization page
+    #[serde(default)]
+    state: String,
+}
+
+/// Localhost server that captures a user token from MusicKit JS
+pub struct UserTokenServer {
+    listener: TcpListener,
+    developer_token: Secret,
+    app_name: String,
+    app_build: String,
+    state: String,
+}
+
+impl UserTokenServer {
+    /// Bind the server on `127.0.0.1` with the given port (0 picks a free port)
+    pub async fn bind<S: Into<Secret>>(developer_token: S, port: u16) -> Result<Self> {
+        let developer_token = developer_token.into();
+        if developer_token.is_empty() {
+            return Err(AppleMusicError::config("Developer token is required"));
+        }
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .await
+            .map_err(|e| AppleMusicError::config(format!("Failed to bind server: {}", e)))?;
+
+        Ok(Self {
+            listener,
+            developer_token,
+            app_name: "apple-music-api".to_string(),
+            app_build: env!("CARGO_PKG_VERSION").to_string(),
+            state: uuid::Uuid::new_v4().to_string(),
+        })
+    }
+
+    /// Set the application name shown by MusicKit JS
+    pub fn with_app_name<S: Into<String>>(mut self, app_name: S) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    /// Set the application build shown by MusicKit JS
+    pub fn with_app_build<S: Into<String>>(mut self, app_build: S) -> Self {
+        self.app_build = app_build.into();
+        self
+    }
+
+    /// Get the address the server is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.listener
+            .local_addr()
+            .map_err(|e| AppleMusicError::config(format!("Failed to get server address: {}", e)))
+    }
+
+    /// Get the URL of the authorization page to open in a browser
+    pub fn url(&self) -> Result<String> {
+        Ok(format!("http://{}/", self.local_addr()?))
+    }
+
+    /// Serve the authorization page until a user token is received or the timeout elapses
+    pub async fn wait_for_token(self, timeout: Duration) -> Result<String> {
+        tokio::time::timeout(timeout, self.serve())
+            .await
+            .map_err(|_| {
+                AppleMusicError::Timeout(format!(
+                    "No user token received within {} seconds",
+                    timeout.as_secs()
+                ))
+            })?
+    }
+
+    /// Accept connections until the callback route receives a token
+    ///
+    /// Each connection is handled on its own task, so a client that never
+    /// finishes its request doesn't hold up the others.
+    async fn serve(&self) -> Result<String> {
+        let page: Arc<str> = self.authorization_page().into();
+        let state: Arc<str> = self.state.as_str().into();
+        let (sender, mut tokens) = mpsc::unbounded_channel();
+
+        loop {
+            let stream = tokio::select! {
+                accepted = self.listener.accept() => {
+                    accepted
+                        .map_err(|e| AppleMusicError::config(format!("Failed to accept: {}", e)))?
+                        .0
+                }
+                Some(token) = tokens.recv() => return Ok(token),
+            };
+
+            let page = Arc::clone(&page);
+            let state = Arc::clone(&state);
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                if let Some(token) = handle_connection(stream, &page, &state).await {
+                    let _ = sender.send(token);
+                }
+            });
+        }
+    }
+
+    /// Render the page that configures MusicKit JS and posts the token back
+    fn authorization_page(&self) -> String {
+        // JSON string literals are valid JavaScript; escape `</` so a value
+        // cannot close the surrounding script element.
+        let js_string = |value: &str| {
+            serde_json::to_string(value)
+                .unwrap_or_default()
+                .replace("</", "<\\/")
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Authorize Apple Music</title>
+<script src="https://js-cdn.music.apple.com/musickit/v3/musickit.js" async></script>
+</head>
+<body>
+<p id="status">Waiting for MusicKit&hellip;</p>
+<button id="authorize">Sign in to Apple Music</button>
+<script>
+document.addEventListener("musickitloaded", async function () {{
+  const music = await MusicKit.configure({{
+    developerToken: {developer_token},
+    app: {{ name: {app_name}, build: {app_build} }}
+  }});
+  const status = document.getElementById("status");
+  status.textContent = "Ready";
+  document.getElementById("authorize").addEventListener("click", async function () {{
+    try {{
+      const musicUserToken = await music.authorize();
+      const response = await fetch({callback_path}, {{
+        method: "POST",
+        headers: {{ "Content-Type": "application/json" }},
+        body: JSON.stringify({{ musicUserToken: musicUserToken, state: {state} }})
+      }});
+      status.textContent = await response.text();
+    }} catch (error) {{
+      status.textContent = "Authorization failed: " + error;
+    }}
+  }});
+}});
+</script>
+</body>
+</html>
+"#,
+            developer_token = js_string(self.developer_token.expose()),
+            app_name = js_string(&self.app_name),
+            app_build = js_string(&self.app_build),
+            callback_path = js_string(CALLBACK_PATH),
+            state = js_string(&self.state),
+        )
+    }
+}
+
+/// Answer a single connection, returning the user token if it delivered one
+async fn handle_connection(mut stream: TcpStream, page: &str, state: &str) -> Option<String> {
+    let request = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(_)) => {
+            let _ = write_response(&mut stream, "400 Bad Request", "text/plain", "").await;
+            return None;
+        }
+        Err(_) => {
+            let _ = write_response(&mut stream, "408 Request Timeout", "text/plain", "").await;
+            return None;
+        }
+    };
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => {
+            let _ = write_response(&mut stream, "200 OK", "text/html", page).await;
+            None
+        }
+        ("POST", CALLBACK_PATH) => match serde_json::from_slice::<CallbackBody>(&request.body) {
+            Ok(body) if body.state != state => {
+                let _ = write_response(&mut stream, "403 Forbidden", "text/plain", "Invalid state")
+                    .await;
+                None
+            }
+            Ok(body) if !body.music_user_token.is_empty() => {
+                let _ = write_response(
+                    &mut stream,
+                    "200 OK",
+                    "text/plain",
+                    "Authorized, you can close this window.",
+                )
+                .await;
+                Some(body.music_user_token.expose().to_string())
+            }
+            _ => {
+                let _ = write_response(
+                    &mut stream,
+                    "400 Bad Request",
+                    "text/plain",
+                    "Missing musicUserToken",
+                )
+                .await;
+                None
+            }
+        },
+        _ => {
+            let _ = write_response(&mut stream, "404 Not Found", "text/plain", "").await;
+            None
+        }
+    }
+}
+
+/// Minimal parsed HTTP request
+struct HttpRequest {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// Read a single HTTP/1.1 request from the stream
+async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buffer.len() > MAX_REQUEST_SIZE {
+            return Err(AppleMusicError::invalid_request(
+                "Request headers too large",
+            ));
+        }
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| AppleMusicError::invalid_request(e.to_string()))?;
+        if read == 0 {
+            return Err(AppleMusicError::invalid_request("Connection closed"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    if content_length > MAX_REQUEST_SIZE {
+        return Err(AppleMusicError::invalid_request("Request body too large"));
+    }
+
+    let mut body = buffer[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| AppleMusicError::invalid_request(e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(HttpRequest { method, path, body })
+}
+
+/// Write a complete HTTP/1.1 response and close the connection
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
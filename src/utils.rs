@@ -1,5 +1,6 @@
 //! Utility functions for Apple Music API client
 
+use crate::clock::{Clock, SharedClock};
 use crate::config::MediaType;
 use crate::error::{AppleMusicError, Result};
 
@@ -48,6 +49,34 @@ pub fn parse_storefront(storefront: &str) -> Result<String> {
     Ok(storefront)
 }
 
+/// Parse a BCP 47 language tag such as `en-US` or `fr-CA`
+pub fn parse_language_tag(language: &str) -> Result<String> {
+    let language = language.trim();
+
+    let valid = !language.is_empty()
+        && language.split('-').enumerate().all(|(i, part)| {
+            let len_ok = if i == 0 {
+                (2..=3).contains(&part.len())
+            } else {
+                (1..=8).contains(&part.len())
+            };
+            len_ok && part.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        && language
+            .split('-')
+            .next()
+            .is_some_and(|primary| primary.chars().all(|c| c.is_ascii_alphabetic()));
+
+    if !valid {
+        return Err(AppleMusicError::invalid_request(format!(
+            "Invalid language tag {:?}",
+            language
+        )));
+    }
+
+    Ok(language.to_string())
+}
+
 /// Validate an Apple Music resource ID
 pub fn validate_resource_id(id: &str) -> Result<()> {
     if id.is_empty() {
@@ -73,6 +102,61 @@ pub fn validate_resource_id(id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Normalize an ISRC such as `us-rc1-76-07839` to `USRC17607839`
+///
+/// Hyphens and spaces are removed and letters are uppercased. The result
+/// must be a 2-letter country code, a 3-character registrant code and 7
+/// digits.
+pub fn normalize_isrc(isrc: &str) -> Result<String> {
+    let normalized: String = isrc
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let valid = normalized.is_ascii()
+        && normalized.len() == 12
+        && normalized[..2].chars().all(|c| c.is_ascii_alphabetic())
+        && normalized[2..5].chars().all(|c| c.is_ascii_alphanumeric())
+        && normalized[5..].chars().all(|c| c.is_ascii_digit());
+
+    if !valid {
+        return Err(AppleMusicError::invalid_request(format!(
+            "Invalid ISRC {:?}",
+            isrc
+        )));
+    }
+
+    Ok(normalized)
+}
+
+/// Normalize a UPC or EAN barcode to its 13-digit EAN-13 form
+///
+/// Spaces and hyphens are removed and leading zeros are adjusted, so the
+/// UPC-A `724384960650`, the EAN-13 `0724384960650` and the GTIN-14
+/// `00724384960650` all normalize to `0724384960650`. Codes must have 8 to
+/// 14 digits.
+pub fn normalize_upc(upc: &str) -> Result<String> {
+    let digits: String = upc
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect();
+
+    let significant = digits.trim_start_matches('0');
+    if digits.len() < 8
+        || !digits.chars().all(|c| c.is_ascii_digit())
+        || digits.len() > 14
+        || significant.len() > 13
+    {
+        return Err(AppleMusicError::invalid_request(format!(
+            "Invalid UPC {:?}",
+            upc
+        )));
+    }
+
+    Ok(format!("{:0>13}", significant))
+}
+
 /// Extract IDs from a list of resource identifiers
 pub fn extract_ids<T: HasId>(items: &[T]) -> Vec<String> {
     items.iter().map(|item| item.id().to_string()).collect()
@@ -133,22 +217,46 @@ pub struct RateLimiter {
     requests_per_second: u32,
     last_request_time: std::time::Instant,
     request_count: u32,
+    clock: SharedClock,
 }
 
 impl RateLimiter {
     /// Create a new rate limiter
     pub fn new(requests_per_second: u32) -> Self {
+        Self::with_clock(requests_per_second, SharedClock::default())
+    }
+
+    /// Create a new rate limiter measuring time with the given clock
+    pub fn with_clock(requests_per_second: u32, clock: SharedClock) -> Self {
         Self {
             requests_per_second,
-            last_request_time: std::time::Instant::now(),
+            last_request_time: clock.instant(),
             request_count: 0,
+            clock,
         }
     }
 
+    /// Check if the next request has to wait, and for how long
+    pub fn delay(&self) -> Option<std::time::Duration> {
+        let elapsed = self.elapsed();
+        if elapsed.as_secs() < 1 && self.request_count >= self.requests_per_second {
+            Some(std::time::Duration::from_secs(1) - elapsed)
+        } else {
+            None
+        }
+    }
+
+    /// Time since the current one-second window started
+    fn elapsed(&self) -> std::time::Duration {
+        self.clock
+            .instant()
+            .saturating_duration_since(self.last_request_time)
+    }
+
     /// Wait if necessary to respect rate limits
     pub async fn wait_if_needed(&mut self) {
-        let now = std::time::Instant::now();
-        let elapsed = now.duration_since(self.last_request_time);
+        let now = self.clock.instant();
+        let elapsed = self.elapsed();
 
         // Reset counter if more than a second has passed
         if elapsed.as_secs() >= 1 {
@@ -158,10 +266,10 @@ impl RateLimiter {
 
         // Check if we need to wait
         if self.request_count >= self.requests_per_second {
-            let wait_time = std::time::Duration::from_secs(1) - elapsed;
+            let wait_time = std::time::Duration::from_secs(1).saturating_sub(elapsed);
             tokio::time::sleep(wait_time).await;
             self.request_count = 0;
-            self.last_request_time = std::time::Instant::now();
+            self.last_request_time = self.clock.instant();
         }
 
         self.request_count += 1;
@@ -294,6 +402,6 @@ impl ResponseMeta {
     /// Check if there are more results
     pub fn has_more(&self, current_count: usize) -> bool {
         self.total
-            .map_or(false, |total| current_count < total as usize)
+            .is_some_and(|total| current_count < total as usize)
     }
 }
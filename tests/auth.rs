@@ -0,0 +1,335 @@
+//! Tests for user token handling against a local stand-in API
+
+mod common;
+
+use apple_music_api::{AppleMusicClient, AppleMusicError};
+use common::{mock_config, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const EMPTY_LIBRARY: &str = r#"{"data":[]}"#;
+const FORBIDDEN: &str = r#"{"errors":[{"code":"40300","detail":"Invalid user token","status":"403","title":"Forbidden"}]}"#;
+
+/// Test that a rejected user token is told apart from a rejected developer token
+#[tokio::test]
+async fn test_user_token_rejection_is_typed() {
+    let server = MockServer::start(|request| match request.path() {
+        "/v1/me/library/songs" => (403, FORBIDDEN.to_string()),
+        _ => (401, String::new()),
+    })
+    .await;
+
+    let mut config = mock_config(server.url());
+    config.user_token = Some("expired-user-token".into());
+    let client = AppleMusicClient::new(config).await.unwrap();
+
+    let error = client.get_library_songs().await.unwrap_err();
+    assert!(error.is_user_token_expired());
+    assert_eq!(error.status_code(), Some(403));
+
+    let error = client.get_storefronts().await.unwrap_err();
+    assert!(matches!(error, AppleMusicError::Api { status: 401, .. }));
+}
+
+/// Test that a catalog 403 doesn't trigger a user token refresh
+#[tokio::test]
+async fn test_catalog_forbidden_is_not_a_user_token_error() {
+    let server = MockServer::start(|_| (403, FORBIDDEN.to_string())).await;
+
+    let mut config = mock_config(server.url());
+    config.user_token = Some("user-token".into());
+    let client = AppleMusicClient::new(config).await.unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    client.set_user_token_refresher(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async { Ok("fresh-user-token".to_string()) }
+    });
+
+    let error = client.get_song("1").await.unwrap_err();
+    assert!(matches!(error, AppleMusicError::Api { status: 403, .. }));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert_eq!(server.requests().len(), 1);
+}
+
+/// Test that the refresh hook is called once and the request retried
+#[tokio::test]
+async fn test_user_token_refresh_retries_request() {
+    let server = MockServer::start(|request| match request.header("music-user-token") {
+        Some("fresh-user-token") => (200, EMPTY_LIBRARY.to_string()),
+        _ => (403, FORBIDDEN.to_string()),
+    })
+    .await;
+
+    let mut config = mock_config(server.url());
+    config.user_token = Some("expired-user-token".into());
+    let client = AppleMusicClient::new(config).await.unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    client.set_user_token_refresher(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async { Ok("fresh-user-token".to_string()) }
+    });
+
+    let songs = client.get_library_songs().await.unwrap();
+    assert!(songs.data.is_empty());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        client.user_token().await.as_deref(),
+        Some("fresh-user-token")
+    );
+
+    // The refreshed token is used directly afterwards
+    client.get_library_songs().await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(server.requests().len(), 3);
+}
+
+/// Test that a token still rejected after refresh is reported without looping
+#[tokio::test]
+async fn test_user_token_refresh_gives_up_after_one_retry() {
+    let server = MockServer::start(|_| (403, FORBIDDEN.to_string())).await;
+
+    let mut config = mock_config(server.url());
+    config.user_token = Some("expired-user-token".into());
+    let client = AppleMusicClient::new(config).await.unwrap();
+    client.set_user_token_refresher(|| async { Ok("still-bad-token".to_string()) });
+
+    let error = client.get_library_songs().await.unwrap_err();
+    assert!(error.is_user_token_expired());
+    assert_eq!(server.requests().len(), 2);
+}
+
+/// Test that developer tokens follow the active signing key
+#[test]
+fn test_signing_key_rotation() {
+    use apple_music_api::auth::AuthManager;
+
+    let mut manager = AuthManager::from_pem(
+        "TEAM_ID".to_string(),
+        "KEY_A".to_string(),
+        common::generate_private_key(),
+    )
+    .unwrap();
+
+    let first = manager.get_developer_token().unwrap().to_string();
+    assert_eq!(key_id_of(&first), "KEY_A");
+
+    manager
+        .add_key("KEY_B".to_string(), common::generate_private_key())
+        .unwrap();
+    assert_eq!(manager.key_ids(), vec!["KEY_A", "KEY_B"]);
+    // Registering a key does not change the token until it is activated
+    assert_eq!(manager.get_developer_token().unwrap(), first);
+
+    manager.set_active_key("KEY_B").unwrap();
+    let second = manager.get_developer_token().unwrap().to_string();
+    assert_eq!(key_id_of(&second), "KEY_B");
+    assert_eq!(manager.current_token_key_id(), Some("KEY_B"));
+
+    assert!(manager.set_active_key("KEY_C").is_err());
+    assert!(manager.remove_key("KEY_B").is_err());
+    manager.remove_key("KEY_A").unwrap();
+    assert_eq!(manager.key_ids(), vec!["KEY_B"]);
+}
+
+/// Test that switching keys on a client changes the token sent with requests
+#[tokio::test]
+async fn test_client_signing_key_rotation() {
+    let server = MockServer::start(|_| (200, r#"{"data":[]}"#.to_string())).await;
+
+    let client = AppleMusicClient::with_jwt_auth(
+        mock_config(server.url()),
+        "TEAM_ID".to_string(),
+        "KEY_A".to_string(),
+        common::generate_private_key(),
+    )
+    .await
+    .unwrap();
+
+    client.get_storefronts().await.unwrap();
+    client
+        .add_signing_key("KEY_B".to_string(), common::generate_private_key())
+        .await
+        .unwrap();
+    client.set_active_signing_key("KEY_B").await.unwrap();
+    client.get_storefronts().await.unwrap();
+
+    let kids: Vec<String> = server
+        .requests()
+        .iter()
+        .map(|request| {
+            let token = request
+                .header("authorization")
+                .unwrap()
+                .trim_start_matches("Bearer ");
+            key_id_of(token)
+        })
+        .collect();
+    assert_eq!(kids, vec!["KEY_A", "KEY_B"]);
+
+    // Clients using a pre-generated token cannot rotate keys
+    let simple = AppleMusicClient::new(mock_config(server.url()))
+        .await
+        .unwrap();
+    assert!(simple.set_active_signing_key("KEY_B").await.is_err());
+}
+
+fn key_id_of(token: &str) -> String {
+    jsonwebtoken::decode_header(token).unwrap().kid.unwrap()
+}
+
+/// Test that tokens and keys never show up in debug output
+#[test]
+fn test_secrets_are_redacted() {
+    use apple_music_api::auth::{AuthConfig, AuthManager, SimpleAuth};
+    use apple_music_api::Secret;
+
+    let private_key = common::generate_private_key();
+    let mut config = mock_config("http://localhost");
+    config.user_token = Some("user-token-value".into());
+
+    let mut manager = AuthManager::from_pem(
+        "TEAM_ID".to_string(),
+        "KEY_ID".to_string(),
+        private_key.clone(),
+    )
+    .unwrap();
+    let developer_token = manager.get_developer_token().unwrap().to_string();
+    manager.set_user_token(Some("user-token-value".to_string()));
+
+    let outputs = [
+        format!("{:?}", config),
+        format!("{:?}", manager),
+        format!(
+            "{:?}",
+            SimpleAuth::new("developer-token").with_user_token("user-token-value".to_string())
+        ),
+        format!(
+            "{:?}",
+            AuthConfig::jwt(
+                "TEAM_ID".to_string(),
+                "KEY_ID".to_string(),
+                private_key.clone()
+            )
+        ),
+    ];
+    for output in &outputs {
+        assert!(output.contains("REDACTED"), "{}", output);
+        assert!(!output.contains("user-token-value"), "{}", output);
+        assert!(!output.contains("developer-token"), "{}", output);
+        assert!(!output.contains(&developer_token), "{}", output);
+        assert!(!output.contains("BEGIN PRIVATE KEY"), "{}", output);
+    }
+
+    let secret = Secret::new("value");
+    assert_eq!(secret.to_string(), "[REDACTED]");
+    assert_eq!(secret.expose(), "value");
+}
+
+/// Test developer token regeneration exactly at the 24 hour refresh boundary
+#[test]
+fn test_developer_token_refresh_boundary() {
+    use apple_music_api::auth::AuthManager;
+    use apple_music_api::clock::{Clock, MockClock, SharedClock};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let clock = MockClock::new(start);
+    let mut manager = AuthManager::from_pem(
+        "TEAM_ID".to_string(),
+        "KEY_ID".to_string(),
+        common::generate_private_key(),
+    )
+    .unwrap()
+    .with_clock(SharedClock::new(clock.clone()));
+
+    let first = manager.get_developer_token().unwrap().to_string();
+    let expires_at = manager.token_expires_at().unwrap();
+    assert_eq!(expires_at, start + Duration::from_secs(15_777_000));
+
+    // Exactly 24 hours before expiry the token is still reused
+    clock.set(expires_at - Duration::from_secs(86_400));
+    assert_eq!(manager.get_developer_token().unwrap(), first);
+    assert_eq!(
+        manager.time_until_expiry(),
+        Some(Duration::from_secs(86_400))
+    );
+
+    // One second later it is regenerated
+    clock.advance(Duration::from_secs(1));
+    let second = manager.get_developer_token().unwrap().to_string();
+    assert_ne!(second, first);
+    assert_eq!(
+        manager.token_expires_at(),
+        Some(clock.now() + Duration::from_secs(15_777_000))
+    );
+
+    clock.set(manager.token_expires_at().unwrap());
+    assert!(manager.is_token_expired());
+}
+
+/// Test that a long-running client signs a new developer token before the old one expires
+#[tokio::test]
+async fn test_client_refreshes_expiring_developer_token() {
+    use apple_music_api::clock::{MockClock, SharedClock};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let server = MockServer::start(|_| (200, EMPTY_LIBRARY.to_string())).await;
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let mut config = mock_config(server.url());
+    config.clock = SharedClock::new(clock.clone());
+    let client = AppleMusicClient::with_jwt_auth(
+        config,
+        "TEAM_ID".to_string(),
+        "KEY_ID".to_string(),
+        common::generate_private_key(),
+    )
+    .await
+    .unwrap();
+
+    let authorization = |index: usize| {
+        server.requests()[index]
+            .header("authorization")
+            .unwrap()
+            .to_string()
+    };
+
+    client.get_storefronts().await.unwrap();
+    clock.advance(Duration::from_secs(86_400));
+    client.get_storefronts().await.unwrap();
+    assert_eq!(authorization(0), authorization(1));
+
+    // Less than a day before expiry, the next request carries a new token
+    clock.advance(Duration::from_secs(15_777_000 - 2 * 86_400 + 1));
+    client.get_storefronts().await.unwrap();
+    assert_ne!(authorization(1), authorization(2));
+    client.get_storefronts().await.unwrap();
+    assert_eq!(authorization(2), authorization(3));
+}
+
+/// Test that the rate limiter window follows the injected clock
+#[test]
+fn test_rate_limiter_uses_clock() {
+    use apple_music_api::clock::{MockClock, SharedClock};
+    use apple_music_api::utils::RateLimiter;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let mut limiter = RateLimiter::with_clock(2, SharedClock::new(clock.clone()));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    runtime.block_on(limiter.wait_if_needed());
+    runtime.block_on(limiter.wait_if_needed());
+
+    clock.advance(Duration::from_millis(400));
+    assert_eq!(limiter.delay(), Some(Duration::from_millis(600)));
+
+    clock.advance(Duration::from_millis(600));
+    assert_eq!(limiter.delay(), None);
+}
@@ -0,0 +1,1169 @@
+//! Tests for catalog requests against a mock server
+
+mod common;
+
+use apple_music_api::config::{ChartOptions, MediaType, QueryOptions, SearchOptions};
+use apple_music_api::{normalize_upc, AppleMusicClient, AppleMusicError};
+use common::MockServer;
+
+/// Serve the `ca` storefront and echo the `l` parameter as the album name
+async fn localized_server() -> MockServer {
+    MockServer::start(|request| {
+        let body = if request.path() == "/v1/storefronts/ca" {
+            serde_json::json!({
+                "data": [common::storefront_json("ca", "en-CA", &["en-CA", "fr-CA"])]
+            })
+        } else if request.path().starts_with("/v1/catalog/ca/search") {
+            serde_json::json!({ "results": {} })
+        } else {
+            let name = request.query_param("l").unwrap_or_default();
+            serde_json::json!({ "data": [common::album_json("1", &name)] })
+        };
+        (200, body.to_string())
+    })
+    .await
+}
+
+/// Test that the configured language is sent as `l` and can be overridden per call
+#[tokio::test]
+async fn test_language_parameter() {
+    let server = localized_server().await;
+    let mut config = common::mock_config(server.url());
+    config.storefront = "ca".to_string();
+    config.language = Some("fr-ca".to_string());
+    let client = AppleMusicClient::new(config).await.unwrap();
+
+    let album = client.get_album("1").await.unwrap();
+    assert_eq!(album.attributes.name, "fr-CA");
+
+    let english = client.with_language("en-CA").await.unwrap();
+    assert_eq!(english.language(), Some("en-CA"));
+    let album = english.get_album("1").await.unwrap();
+    assert_eq!(album.attributes.name, "en-CA");
+
+    // A language in the options applies to that call only
+    let options = QueryOptions::new().with_language("en-CA");
+    let album = client.get_album_with_options("1", &options).await.unwrap();
+    assert_eq!(album.attributes.name, "en-CA");
+    let album = client.get_album("1").await.unwrap();
+    assert_eq!(album.attributes.name, "fr-CA");
+    assert!(client
+        .get_album_with_options("1", &QueryOptions::new().with_language("de-DE"))
+        .await
+        .is_err());
+
+    client
+        .search_with_options(
+            "hello",
+            &[MediaType::Songs],
+            &SearchOptions::new().with_language("en-CA"),
+        )
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    let search = requests
+        .iter()
+        .find(|request| request.path() == "/v1/catalog/ca/search")
+        .expect("search should use the configured storefront");
+    assert_eq!(search.query_param("l").as_deref(), Some("en-CA"));
+
+    // The supported languages are fetched once
+    let storefront_requests = requests
+        .iter()
+        .filter(|request| request.path() == "/v1/storefronts/ca")
+        .count();
+    assert_eq!(storefront_requests, 1);
+}
+
+/// Test that languages the storefront does not support are rejected
+#[tokio::test]
+async fn test_unsupported_language() {
+    let server = localized_server().await;
+    let mut config = common::mock_config(server.url());
+    config.storefront = "ca".to_string();
+    let client = AppleMusicClient::new(config).await.unwrap();
+
+    let Err(error) = client.with_language("de-DE").await else {
+        panic!("de-DE should be rejected");
+    };
+    let AppleMusicError::InvalidRequest(message) = error else {
+        panic!("Expected invalid request error, got {:?}", error);
+    };
+    assert!(message.contains("en-CA, fr-CA"), "{}", message);
+
+    // Without a language no `l` parameter is sent
+    client.get_album("1").await.unwrap();
+    let album_request = server.requests().pop().unwrap();
+    assert_eq!(album_request.query_param("l"), None);
+}
+
+/// Serve `v1/me/storefront` according to the user token
+async fn user_storefront_server() -> MockServer {
+    MockServer::start(|request| {
+        if request.path() == "/v1/me/storefront" {
+            let storefront = match request.header("music-user-token") {
+                Some("french-user") => "fr",
+                Some("japanese-user") => "jp",
+                _ => return (401, String::new()),
+            };
+            let body = serde_json::json!({
+                "data": [common::storefront_json(storefront, "en-US", &["en-US"])]
+            });
+            return (200, body.to_string());
+        }
+        let body = serde_json::json!({ "data": [common::album_json("1", "Album")] });
+        (200, body.to_string())
+    })
+    .await
+}
+
+/// Test switching catalog requests to the user's storefront
+#[tokio::test]
+async fn test_use_user_storefront() {
+    let server = user_storefront_server().await;
+    let mut config = common::mock_config(server.url());
+    config.use_user_storefront = true;
+    let mut client = AppleMusicClient::new(config).await.unwrap();
+    assert_eq!(client.storefront(), "us");
+
+    client
+        .set_user_token(Some("french-user".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(client.storefront(), "fr");
+    client.get_album("1").await.unwrap();
+    assert_eq!(
+        server.requests().last().unwrap().path(),
+        "/v1/catalog/fr/albums/1"
+    );
+
+    client.set_user_token(None).await.unwrap();
+    assert_eq!(client.storefront(), "us");
+}
+
+/// Test that a user token from the refresh hook moves requests to its storefront
+#[tokio::test]
+async fn test_user_storefront_follows_refreshed_token() {
+    let server = MockServer::start(|request| {
+        let user_token = request.header("music-user-token");
+        if request.path() == "/v1/me/storefront" {
+            let storefront = match user_token {
+                Some("french-user") => "fr",
+                Some("japanese-user") => "jp",
+                _ => return (401, String::new()),
+            };
+            let body = serde_json::json!({
+                "data": [common::storefront_json(storefront, "en-US", &["en-US"])]
+            });
+            return (200, body.to_string());
+        }
+        if request.path().starts_with("/v1/me/") && user_token != Some("japanese-user") {
+            return (401, String::new());
+        }
+        let body = serde_json::json!({ "data": [common::album_json("1", "Album")] });
+        (200, body.to_string())
+    })
+    .await;
+
+    let mut config = common::mock_config(server.url());
+    config.use_user_storefront = true;
+    config.user_token = Some("french-user".into());
+    let client = AppleMusicClient::new(config).await.unwrap();
+    assert_eq!(client.storefront(), "fr");
+
+    client.set_user_token_refresher(|| async { Ok("japanese-user".to_string()) });
+    client.get_library_albums().await.unwrap();
+    assert_eq!(client.storefront(), "jp");
+
+    client.get_album("1").await.unwrap();
+    assert_eq!(
+        server.requests().last().unwrap().path(),
+        "/v1/catalog/jp/albums/1"
+    );
+}
+
+/// Test looking up the storefront of a given user
+#[tokio::test]
+async fn test_storefront_for_user_token() {
+    let server = user_storefront_server().await;
+    let mut client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    assert!(client.get_user_storefront().await.is_err());
+
+    client
+        .set_user_token(Some("french-user".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(client.get_user_storefront().await.unwrap().id, "fr");
+    let storefront = client
+        .get_storefront_for_user_token("japanese-user")
+        .await
+        .unwrap();
+    assert_eq!(storefront.id, "jp");
+
+    // Without the option the client keeps its configured storefront
+    assert_eq!(client.storefront(), "us");
+}
+
+/// Test that every page of an album's tracks is fetched
+#[tokio::test]
+async fn test_album_tracks_follow_next() {
+    let server = MockServer::start(|request| {
+        let tracks_path = "/v1/catalog/us/albums/1/tracks";
+        if request.path() == tracks_path {
+            return (200, common::song_page(request, tracks_path, 250, 100));
+        }
+        // The album includes the first page of its tracks
+        let mut album = common::album_json("1", "Box Set");
+        let first_page: serde_json::Value =
+            serde_json::from_str(&common::song_page(request, tracks_path, 250, 100)).unwrap();
+        album["relationships"] = serde_json::json!({ "tracks": first_page });
+        (200, serde_json::json!({ "data": [album] }).to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let tracks = client.get_album_tracks("1").await.unwrap();
+    assert_eq!(tracks.len(), 250);
+    assert_eq!(tracks[249].id, "250");
+
+    let album = client.get_album_with_tracks("1").await.unwrap();
+    let tracks = album.relationships.unwrap().tracks.unwrap();
+    assert_eq!(tracks.data.len(), 250);
+    assert!(tracks.next.is_none());
+
+    let album_request = server
+        .requests()
+        .into_iter()
+        .find(|request| request.path() == "/v1/catalog/us/albums/1")
+        .unwrap();
+    assert_eq!(
+        album_request.query_param("include").as_deref(),
+        Some("tracks")
+    );
+}
+
+/// Test artist relationship and view endpoints
+#[tokio::test]
+async fn test_artist_relationships_and_views() {
+    let server = MockServer::start(|request| {
+        let path = request.path().to_string();
+        let body = match path.trim_start_matches("/v1/catalog/us/artists/1/") {
+            "view/top-songs" => return (200, common::song_page(request, &path, 30, 10)),
+            "albums" | "view/singles" => serde_json::json!({
+                "data": [common::album_json("10", "Album")]
+            }),
+            "view/similar-artists" => serde_json::json!({
+                "data": [common::artist_json("2", "Similar")]
+            }),
+            "genres" => serde_json::json!({
+                "data": [{ "id": "14", "type": "genres", "attributes": { "name": "Pop" } }]
+            }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let top_songs = client.get_artist_top_songs("1").await.unwrap();
+    assert_eq!(top_songs.len(), 30);
+    assert_eq!(client.get_artist_albums("1").await.unwrap()[0].id, "10");
+    assert_eq!(client.get_artist_singles("1").await.unwrap().len(), 1);
+    assert_eq!(
+        client.get_artist_similar_artists("1").await.unwrap()[0]
+            .attributes
+            .name,
+        "Similar"
+    );
+    assert_eq!(client.get_artist_genres("1").await.unwrap()[0].id, "14");
+    assert!(client.get_artist_playlists("bad id").await.is_err());
+}
+
+/// Test batch ISRC lookup with chunking and grouping
+#[tokio::test]
+async fn test_songs_by_isrcs() {
+    let server = MockServer::start(|request| {
+        let isrcs = request.query_param("filter[isrc]").unwrap_or_default();
+        let mut data = Vec::new();
+        for isrc in isrcs.split(',').filter(|isrc| !isrc.ends_with("9")) {
+            let mut song = common::song_json(&isrc[5..], "Song");
+            song["attributes"]["isrc"] = isrc.into();
+            data.push(song.clone());
+            if isrc == "USAAA0000001" {
+                // A second release of the same recording
+                song["id"] = "duplicate".into();
+                data.push(song);
+            }
+        }
+        (200, serde_json::json!({ "data": data }).to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let isrcs: Vec<String> = (0..30).map(|i| format!("us-aaa-00-{:05}", i)).collect();
+    let isrcs: Vec<&str> = isrcs.iter().map(String::as_str).collect();
+    let matches = client.get_songs_by_isrcs(&isrcs).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0]
+            .query_param("filter[isrc]")
+            .unwrap()
+            .split(',')
+            .count(),
+        25
+    );
+    assert_eq!(matches.len(), 30);
+    assert_eq!(matches["USAAA0000001"].len(), 2);
+    assert_eq!(matches["USAAA0000002"].len(), 1);
+    assert!(matches["USAAA0000009"].is_empty());
+
+    assert_eq!(
+        client
+            .get_songs_by_isrc("USAAA0000001")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(client.get_songs_by_isrc("not-an-isrc").await.is_err());
+    assert!(client.get_songs_by_isrc("Aé123456789").await.is_err());
+}
+
+/// Test batch UPC lookup keyed by normalized UPC
+#[tokio::test]
+async fn test_albums_by_upcs() {
+    let server = MockServer::start(|request| {
+        let upcs = request.query_param("filter[upc]").unwrap_or_default();
+        let data: Vec<_> = upcs
+            .split(',')
+            // The catalog lists each album under one spelling only
+            .filter(|upc| *upc == "724384960650" || *upc == "5099902894324")
+            .map(|upc| {
+                let mut album = common::album_json(upc, "Album");
+                album["attributes"]["upc"] = upc.into();
+                album
+            })
+            .collect();
+        (200, serde_json::json!({ "data": data }).to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let matches = client
+        .get_albums_by_upcs(&[
+            "0724384960650",
+            "00724384960650",
+            "5099902894324",
+            "12345670",
+        ])
+        .await
+        .unwrap();
+    assert_eq!(matches.len(), 3);
+    assert_eq!(matches["0724384960650"][0].id, "724384960650");
+    assert_eq!(matches["5099902894324"].len(), 1);
+    assert!(matches["0000012345670"].is_empty());
+    assert!(matches.contains_key(&normalize_upc("724384960650").unwrap()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].query_param("filter[upc]").as_deref(),
+        Some("724384960650,0724384960650,5099902894324,000012345670,0000012345670")
+    );
+
+    assert_eq!(
+        client
+            .get_albums_by_upc("724384960650")
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(client.get_albums_by_upc("12ab").await.is_err());
+
+    // Codes shorter than EAN-8 are rejected
+    assert!(normalize_upc("1234567").is_err());
+    assert!(client.get_albums_by_upc("123").await.is_err());
+}
+
+/// Test fetching charts and following a chart's pages
+#[tokio::test]
+async fn test_charts() {
+    let server = MockServer::start(|request| {
+        let offset: usize = request
+            .query_param("offset")
+            .and_then(|offset| offset.parse().ok())
+            .unwrap_or(0);
+        let songs: Vec<_> = (offset + 1..=offset + 2)
+            .map(|i| common::song_json(&i.to_string(), "Song"))
+            .collect();
+        let mut song_chart = serde_json::json!({
+            "chart": "most-played",
+            "name": "Top Songs",
+            "orderId": "most-played:songs",
+            "data": songs,
+            "href": "/v1/catalog/us/charts?chart=most-played&types=songs"
+        });
+        if offset < 4 {
+            song_chart["next"] =
+                format!("/v1/catalog/us/charts?types=songs&offset={}", offset + 2).into();
+        }
+        let mut results = serde_json::json!({ "songs": [song_chart] });
+        if request.query_param("types").as_deref() == Some("songs,albums") {
+            results["albums"] = serde_json::json!([{
+                "chart": "most-played",
+                "name": "Top Albums",
+                "data": [common::album_json("1", "Album")]
+            }]);
+        }
+        let body = serde_json::json!({
+            "results": results,
+            "meta": { "results": { "order": ["most-played:songs", "most-played:albums"] } }
+        });
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let options = ChartOptions::new(vec![MediaType::Songs, MediaType::Albums])
+        .with_chart("most-played")
+        .with_genre("20")
+        .with_limit(2);
+    let charts = client.get_charts_with_options(&options).await.unwrap();
+    let request = &server.requests()[0];
+    assert_eq!(request.path(), "/v1/catalog/us/charts");
+    assert_eq!(request.query_param("genre").as_deref(), Some("20"));
+    assert_eq!(request.query_param("chart").as_deref(), Some("most-played"));
+    assert_eq!(charts.results.albums.unwrap()[0].name, "Top Albums");
+    assert_eq!(charts.meta.unwrap().results.unwrap().order.len(), 2);
+
+    let song_chart = charts.results.songs.unwrap().remove(0);
+    assert_eq!(song_chart.order_id.as_deref(), Some("most-played:songs"));
+    let full = client.get_full_chart(song_chart).await.unwrap();
+    let ids: Vec<_> = full.data.iter().map(|song| song.id.as_str()).collect();
+    assert_eq!(ids, ["1", "2", "3", "4", "5", "6"]);
+    assert!(full.next.is_none());
+
+    assert!(client.get_charts(&[MediaType::Artists]).await.is_err());
+}
+
+/// Test genre list, lookup, batch fetch and relationships
+#[tokio::test]
+async fn test_genres() {
+    let server = MockServer::start(|request| {
+        let body = match request.path() {
+            "/v1/catalog/us/genres" => match request.query_param("ids") {
+                Some(ids) => serde_json::json!({
+                    "data": ids.split(',').map(|id| common::genre_json(id, "Genre")).collect::<Vec<_>>()
+                }),
+                None if request.query_param("offset").is_none() => serde_json::json!({
+                    "data": [common::genre_json("34", "Music")],
+                    "next": "/v1/catalog/us/genres?offset=1"
+                }),
+                None => serde_json::json!({ "data": [common::genre_json("14", "Pop")] }),
+            },
+            "/v1/catalog/us/genres/14" => serde_json::json!({
+                "data": [common::genre_json("14", "Pop")]
+            }),
+            "/v1/catalog/us/genres/14/stations" => serde_json::json!({
+                "data": [common::station_json("ra.1", "Pop Station")]
+            }),
+            "/v1/catalog/us/charts" => serde_json::json!({ "results": {} }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let genres = client.get_storefront_genres().await.unwrap();
+    assert_eq!(genres.len(), 2);
+    let pop = client.get_genre("14").await.unwrap();
+    assert_eq!(pop.attributes.unwrap().name, "Pop");
+    assert_eq!(client.get_genres(&["14", "21"]).await.unwrap().len(), 2);
+    assert_eq!(
+        client.get_genre_stations("14").await.unwrap()[0]
+            .attributes
+            .name,
+        "Pop Station"
+    );
+
+    client
+        .get_genre_charts("14", &[MediaType::Songs])
+        .await
+        .unwrap();
+    let charts_request = server.requests().pop().unwrap();
+    assert_eq!(charts_request.query_param("genre").as_deref(), Some("14"));
+}
+
+/// Test station, live radio and station genre endpoints
+#[tokio::test]
+async fn test_stations() {
+    let server = MockServer::start(|request| {
+        let body = match request.path() {
+            "/v1/catalog/us/stations/ra.1" => {
+                let mut station = common::station_json("ra.1", "Apple Music 1");
+                station["attributes"]["isLive"] = true.into();
+                station["attributes"]["requiresSubscription"] = false.into();
+                station["attributes"]["stationProviderName"] = "Apple Music".into();
+                station["attributes"]["playParams"] =
+                    serde_json::json!({ "id": "ra.1", "kind": "radioStation" });
+                serde_json::json!({ "data": [station] })
+            }
+            "/v1/catalog/us/stations" => {
+                let ids = match request.query_param("filter[featured]").as_deref() {
+                    Some("apple-music-live-radio") => "ra.1,ra.2,ra.3".to_string(),
+                    _ => request.query_param("ids").unwrap_or_default(),
+                };
+                serde_json::json!({
+                    "data": ids.split(',').map(|id| common::station_json(id, "Station")).collect::<Vec<_>>()
+                })
+            }
+            "/v1/catalog/us/station-genres" => serde_json::json!({
+                "data": [{ "id": "1", "type": "station-genres", "attributes": { "name": "Pop" } }]
+            }),
+            "/v1/catalog/us/station-genres/1/stations" => serde_json::json!({
+                "data": [common::station_json("ra.4", "Pop Radio")]
+            }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let station = client.get_station("ra.1").await.unwrap();
+    assert!(station.attributes.is_live);
+    assert_eq!(station.attributes.requires_subscription, Some(false));
+    assert_eq!(
+        station.attributes.station_provider_name.as_deref(),
+        Some("Apple Music")
+    );
+    assert!(station.attributes.play_params.is_some());
+
+    assert_eq!(
+        client.get_stations(&["ra.1", "ra.2"]).await.unwrap().len(),
+        2
+    );
+    assert_eq!(client.get_live_radio_stations().await.unwrap().len(), 3);
+
+    let genres = client.get_storefront_station_genres().await.unwrap();
+    assert_eq!(genres[0].attributes.as_ref().unwrap().name, "Pop");
+    let stations = client
+        .get_station_genre_stations(&genres[0].id)
+        .await
+        .unwrap();
+    assert_eq!(stations[0].id, "ra.4");
+}
+
+/// Test curator lookups, playlists and playlist curator resolution
+#[tokio::test]
+async fn test_curators() {
+    let server = MockServer::start(|request| {
+        let path = request.path().to_string();
+        let body = match path.as_str() {
+            "/v1/catalog/us/curators/1" => serde_json::json!({
+                "data": [common::curator_json("1", "curators", "Curator")]
+            }),
+            "/v1/catalog/us/apple-curators" => serde_json::json!({
+                "data": request.query_param("ids").unwrap_or_default().split(',')
+                    .map(|id| common::curator_json(id, "apple-curators", "Apple Curator"))
+                    .collect::<Vec<_>>()
+            }),
+            "/v1/catalog/us/curators/1/playlists" => {
+                let offset = request.query_param("offset");
+                let mut body = serde_json::json!({
+                    "data": [common::playlist_json(
+                        if offset.is_some() { "pl.2" } else { "pl.1" },
+                        "Playlist"
+                    )]
+                });
+                if offset.is_none() {
+                    body["next"] = format!("{}?offset=1", path).into();
+                }
+                body
+            }
+            "/v1/catalog/us/playlists/pl.1/curator" => serde_json::json!({
+                "data": [common::curator_json("976439548", "apple-curators", "Apple Music Pop")]
+            }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client.get_curator("1").await.unwrap().attributes.name,
+        "Curator"
+    );
+    let apple_curators = client.get_apple_curators(&["2", "3"]).await.unwrap();
+    assert_eq!(apple_curators.len(), 2);
+    assert_eq!(apple_curators[0].resource_type, "apple-curators");
+
+    let playlists = client.get_curator_playlists("1").await.unwrap();
+    let ids: Vec<_> = playlists
+        .iter()
+        .map(|playlist| playlist.id.as_str())
+        .collect();
+    assert_eq!(ids, ["pl.1", "pl.2"]);
+
+    let curator = client.get_playlist_curator("pl.1").await.unwrap();
+    assert_eq!(curator.id, "976439548");
+    assert_eq!(curator.resource_type, "apple-curators");
+}
+
+/// Test activity and record label endpoints
+#[tokio::test]
+async fn test_activities_and_record_labels() {
+    let server = MockServer::start(|request| {
+        let resource = |id: &str, resource_type: &str, name: &str| {
+            serde_json::json!({
+                "id": id,
+                "type": resource_type,
+                "attributes": {
+                    "name": name,
+                    "url": format!("https://music.apple.com/us/{}/{}", resource_type, id)
+                }
+            })
+        };
+        let body = match request.path() {
+            "/v1/catalog/us/activities/976439514" => serde_json::json!({
+                "data": [resource("976439514", "activities", "Workout")]
+            }),
+            "/v1/catalog/us/activities" => serde_json::json!({
+                "data": request.query_param("ids").unwrap_or_default().split(',')
+                    .map(|id| resource(id, "activities", "Activity"))
+                    .collect::<Vec<_>>()
+            }),
+            "/v1/catalog/us/activities/976439514/playlists" => serde_json::json!({
+                "data": [common::playlist_json("pl.1", "Pure Workout")]
+            }),
+            "/v1/catalog/us/record-labels/1543411840" => serde_json::json!({
+                "data": [resource("1543411840", "record-labels", "Label")]
+            }),
+            "/v1/catalog/us/record-labels/1543411840/view/latest-releases"
+            | "/v1/catalog/us/record-labels/1543411840/view/top-releases" => serde_json::json!({
+                "data": [common::album_json("1", "Release")]
+            }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let activity = client.get_activity("976439514").await.unwrap();
+    assert_eq!(activity.attributes.name, "Workout");
+    assert_eq!(client.get_activities(&["1", "2"]).await.unwrap().len(), 2);
+    assert_eq!(
+        client.get_activity_playlists("976439514").await.unwrap()[0].id,
+        "pl.1"
+    );
+
+    let label = client.get_record_label("1543411840").await.unwrap();
+    assert_eq!(label.resource_type, "record-labels");
+    assert_eq!(
+        client
+            .get_record_label_latest_releases("1543411840")
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        client
+            .get_record_label_top_releases("1543411840")
+            .await
+            .unwrap()[0]
+            .attributes
+            .name,
+        "Release"
+    );
+
+    let error = client.get_record_label("404").await.unwrap_err();
+    assert_eq!(error.status_code(), Some(404));
+}
+
+/// Test music video lookups, relationships, views and ISRC lookup
+#[tokio::test]
+async fn test_music_videos() {
+    let server = MockServer::start(|request| {
+        let path = request.path().to_string();
+        let body = match path.trim_start_matches("/v1/catalog/us/music-videos") {
+            "/1" => serde_json::json!({ "data": [common::music_video_json("1", "Video")] }),
+            "" => {
+                let ids = match request.query_param("filter[isrc]") {
+                    Some(isrc) => vec![isrc[5..].trim_start_matches('0').to_string()],
+                    None => request
+                        .query_param("ids")
+                        .unwrap_or_default()
+                        .split(',')
+                        .map(str::to_string)
+                        .collect(),
+                };
+                serde_json::json!({
+                    "data": ids.iter().map(|id| common::music_video_json(id, "Video")).collect::<Vec<_>>()
+                })
+            }
+            "/1/albums" => serde_json::json!({ "data": [common::album_json("10", "Album")] }),
+            "/1/artists" => serde_json::json!({ "data": [common::artist_json("20", "Artist")] }),
+            "/1/songs" => serde_json::json!({ "data": [common::song_json("30", "Song")] }),
+            "/1/genres" => serde_json::json!({ "data": [common::genre_json("14", "Pop")] }),
+            "/1/view/more-by-artist" => {
+                // Two pages of music videos
+                let offset = request.query_param("offset");
+                let id = if offset.is_some() { "3" } else { "2" };
+                let mut body = serde_json::json!({ "data": [common::music_video_json(id, "More")] });
+                if offset.is_none() {
+                    body["next"] = format!("{}?offset=1", path).into();
+                }
+                body
+            }
+            "/1/view/more-in-genre" => serde_json::json!({ "data": [] }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client.get_music_video("1").await.unwrap().attributes.name,
+        "Video"
+    );
+    assert_eq!(client.get_music_videos(&["1", "2"]).await.unwrap().len(), 2);
+    assert_eq!(
+        client.get_music_video_albums("1").await.unwrap()[0].id,
+        "10"
+    );
+    assert_eq!(
+        client.get_music_video_artists("1").await.unwrap()[0].id,
+        "20"
+    );
+    assert_eq!(client.get_music_video_songs("1").await.unwrap()[0].id, "30");
+    assert_eq!(
+        client.get_music_video_genres("1").await.unwrap()[0].id,
+        "14"
+    );
+    assert_eq!(
+        client
+            .get_music_video_more_by_artist("1")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(client
+        .get_music_video_more_in_genre("1")
+        .await
+        .unwrap()
+        .is_empty());
+
+    let videos = client
+        .get_music_videos_by_isrc("USAAA0000007")
+        .await
+        .unwrap();
+    assert_eq!(videos[0].id, "7");
+}
+
+/// Test fetching resources of several types in one request
+#[tokio::test]
+async fn test_catalog_resources_by_type() {
+    let server = MockServer::start(|request| {
+        if request.path() != "/v1/catalog/us" {
+            return (404, String::new());
+        }
+        let ids = |key: &str| -> Vec<String> {
+            request
+                .query_param(key)
+                .unwrap_or_default()
+                .split(',')
+                .filter(|id| !id.is_empty() && *id != "999")
+                .map(str::to_string)
+                .collect()
+        };
+        let mut data: Vec<serde_json::Value> = Vec::new();
+        data.extend(
+            ids("ids[songs]")
+                .iter()
+                .map(|id| common::song_json(id, "Song")),
+        );
+        data.extend(
+            ids("ids[albums]")
+                .iter()
+                .map(|id| common::album_json(id, "Album")),
+        );
+        data.extend(
+            ids("ids[playlists]")
+                .iter()
+                .map(|id| common::playlist_json(id, "Playlist")),
+        );
+        (200, serde_json::json!({ "data": data }).to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let resources = client
+        .get_catalog_resources_by_type(&[
+            (MediaType::Songs, &["1", "2", "999"]),
+            (MediaType::Albums, &["10"]),
+            (MediaType::Playlists, &["pl.1"]),
+            (MediaType::Songs, &["2", "3"]),
+        ])
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.query_param("ids[songs]").unwrap(), "1,2,999,3");
+    assert_eq!(request.query_param("ids[albums]").unwrap(), "10");
+    assert_eq!(resources.ids(MediaType::Songs), ["1", "2", "3"]);
+    assert_eq!(resources.albums[0].id, "10");
+    assert_eq!(resources.playlists[0].id, "pl.1");
+    assert!(!resources.is_complete());
+    assert_eq!(resources.missing.len(), 1);
+    assert_eq!(resources.missing[&MediaType::Songs], ["999"]);
+
+    assert!(client
+        .get_catalog_resources_by_type(&[(MediaType::Albums, &[])])
+        .await
+        .unwrap()
+        .is_complete());
+    assert_eq!(server.requests().len(), 1);
+}
+
+/// Test per-call query options and the attributes they leave out
+#[tokio::test]
+async fn test_query_options() {
+    let server = MockServer::start(|request| {
+        let body = match request.path() {
+            "/v1/catalog/us/albums/1" => {
+                let mut album = serde_json::json!({
+                    "id": "1",
+                    "type": "albums",
+                    "attributes": {
+                        "name": "Sparse",
+                        "editorialArtwork": {
+                            "subscriptionHero": { "url": "https://example.com/hero.jpg" }
+                        }
+                    },
+                    "relationships": {
+                        "artists": { "data": [common::artist_json("20", "Artist")] },
+                        "record-labels": {
+                            "data": [{
+                                "id": "30",
+                                "type": "record-labels",
+                                "attributes": { "name": "Label", "url": "https://example.com" }
+                            }]
+                        }
+                    },
+                    "views": {
+                        "other-versions": {
+                            "attributes": { "title": "Other Versions" },
+                            "data": [common::album_json("2", "Deluxe")]
+                        }
+                    }
+                });
+                let include = request.query_param("include").unwrap_or_default();
+                if include.split(',').any(|include| include == "tracks") {
+                    album["relationships"]["tracks"] =
+                        serde_json::json!({ "data": [common::song_json("5", "Track")] });
+                }
+                serde_json::json!({ "data": [album] })
+            }
+            "/v1/catalog/us/artists/20/albums" => {
+                serde_json::json!({ "data": [common::album_json("1", "Album")] })
+            }
+            "/v1/me/library/songs" => serde_json::json!({
+                "data": [{
+                    "id": "i.1",
+                    "type": "library-songs",
+                    "attributes": { "name": "Library Song", "genreNames": [], "hasLyrics": false },
+                    "relationships": {
+                        "catalog": { "data": [common::song_json("7", "Catalog Song")] }
+                    }
+                }]
+            }),
+            _ => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let mut config = common::mock_config(server.url());
+    config.user_token = Some("user-token".into());
+    let client = AppleMusicClient::new(config).await.unwrap();
+
+    let options = QueryOptions::new()
+        .with_include(&["artists", "record-labels"])
+        .with_extend(&["editorialArtwork"])
+        .with_fields("albums", &["name", "editorialArtwork"])
+        .with_views(&["other-versions"]);
+    let album = client.get_album_with_options("1", &options).await.unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(
+        request.query_param("include").as_deref(),
+        Some("artists,record-labels")
+    );
+    assert_eq!(
+        request.query_param("extend").as_deref(),
+        Some("editorialArtwork")
+    );
+    assert_eq!(
+        request.query_param("fields[albums]").as_deref(),
+        Some("name,editorialArtwork")
+    );
+    assert_eq!(
+        request.query_param("views").as_deref(),
+        Some("other-versions")
+    );
+
+    // Attributes left out by the sparse fieldset keep their defaults
+    assert_eq!(album.attributes.name, "Sparse");
+    assert!(album.attributes.artist_name.is_empty());
+    assert!(
+        album.attributes.editorial_artwork.unwrap()["subscriptionHero"]
+            .url
+            .ends_with("hero.jpg")
+    );
+    let relationships = album.relationships.unwrap();
+    assert_eq!(relationships.artists.unwrap().data[0].id, "20");
+    assert_eq!(
+        relationships.record_labels.unwrap().data[0].attributes.name,
+        "Label"
+    );
+    let view = &album.views.unwrap()["other-versions"];
+    assert_eq!(
+        view.attributes.as_ref().unwrap().title.as_deref(),
+        Some("Other Versions")
+    );
+    assert_eq!(
+        view.items::<apple_music_api::catalog::Album>().unwrap()[0].id,
+        "2"
+    );
+
+    // Options apply to a single call only
+    client.get_album("1").await.unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.query_param("include"), None);
+    assert_eq!(request.query_param("fields[albums]"), None);
+
+    let album = client.get_album_with_tracks("1").await.unwrap();
+    assert_eq!(album.relationships.unwrap().tracks.unwrap().data[0].id, "5");
+
+    // `tracks` is merged into the requested relationships
+    let album = client
+        .get_album_with_tracks_with_options("1", &QueryOptions::new().with_include(&["artists"]))
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(
+        request.query_param("include").as_deref(),
+        Some("artists,tracks")
+    );
+    let relationships = album.relationships.unwrap();
+    assert_eq!(relationships.artists.unwrap().data[0].id, "20");
+    assert_eq!(relationships.tracks.unwrap().data[0].id, "5");
+
+    let albums = client
+        .get_artist_albums_with_options("20", &QueryOptions::new().with_fields("albums", &["name"]))
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(
+        request.query_param("fields[albums]").as_deref(),
+        Some("name")
+    );
+    assert_eq!(albums[0].id, "1");
+
+    let songs = client
+        .get_library_songs_with_options(&QueryOptions::new().with_include(&["catalog"]))
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.query_param("include").as_deref(), Some("catalog"));
+    let catalog = songs.data[0]
+        .relationships
+        .as_ref()
+        .unwrap()
+        .catalog
+        .as_ref();
+    assert_eq!(catalog.unwrap().data[0].id, "7");
+}
+
+/// Test mapping IDs to their equivalents in another storefront
+#[tokio::test]
+async fn test_equivalents() {
+    let server = MockServer::start(|request| {
+        // "1" keeps its ID in jp, "2" becomes "200" and "3" has no equivalent
+        let equivalents = request
+            .query_param("filter[equivalents]")
+            .unwrap_or_default();
+        let ids: Vec<&str> = equivalents
+            .split(',')
+            .filter(|id| ["1", "2"].contains(id))
+            .collect();
+        let data: Vec<serde_json::Value> = match request.path() {
+            "/v1/storefronts/jp" => vec![common::storefront_json("jp", "ja", &["ja", "en-US"])],
+            "/v1/catalog/us/songs" => request
+                .query_param("ids")
+                .unwrap_or_default()
+                .split(',')
+                .map(|id| common::song_json(id, "Song"))
+                .collect(),
+            "/v1/catalog/jp/songs" => ids
+                .iter()
+                .map(|id| {
+                    // The renamed song keeps the ISRC of the original
+                    let mut song = common::song_json(id, "Song");
+                    if *id == "2" {
+                        song["id"] = "200".into();
+                    }
+                    song
+                })
+                .collect(),
+            "/v1/catalog/jp/albums" => ids
+                .iter()
+                .map(|id| common::album_json(id, "Album"))
+                .collect(),
+            _ => return (404, String::new()),
+        };
+        (200, serde_json::json!({ "data": data }).to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let songs = client
+        .get_equivalent_songs(&["1", "2", "3", "1"], "JP")
+        .await
+        .unwrap();
+    assert_eq!(songs.storefront, "jp");
+    assert_eq!(songs.get("1").unwrap().id, "1");
+    assert_eq!(songs.get("2").unwrap().id, "200");
+    assert_eq!(songs.unmatched, ["3"]);
+    assert!(!songs.is_complete());
+
+    // One batch, then the IDs it left unresolved fetched together to match ISRCs
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].query_param("filter[equivalents]").as_deref(),
+        Some("1,2,3")
+    );
+    assert_eq!(requests[1].path(), "/v1/catalog/us/songs");
+    assert_eq!(requests[1].query_param("ids").as_deref(), Some("2,3"));
+
+    let albums = client.get_equivalent_albums(&["1"], "jp").await.unwrap();
+    assert!(albums.is_complete());
+    assert_eq!(server.requests().len(), 3);
+
+    // The language is checked against the target storefront
+    let options = QueryOptions::new().with_language("EN-us");
+    client
+        .get_equivalent_albums_with_options(&["1"], "jp", &options)
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.query_param("l").as_deref(), Some("en-US"));
+    let options = QueryOptions::new().with_language("fr-FR");
+    let Err(error) = client
+        .get_equivalent_albums_with_options(&["1"], "jp", &options)
+        .await
+    else {
+        panic!("expected an unsupported language error");
+    };
+    assert!(matches!(error, AppleMusicError::InvalidRequest(_)));
+
+    let Err(error) = client.get_equivalent_songs(&["1"], "zz").await else {
+        panic!("expected an unknown storefront error");
+    };
+    assert!(matches!(error, AppleMusicError::InvalidRequest(_)));
+}
+
+/// Test that every page of a catalog playlist's tracks is fetched
+#[tokio::test]
+async fn test_playlist_tracks_follow_next() {
+    use futures_util::{StreamExt, TryStreamExt};
+
+    let server = MockServer::start(|request| {
+        let tracks_path = "/v1/catalog/us/playlists/pl.1/tracks";
+        match request.path() {
+            path if path == tracks_path => (200, common::song_page(request, tracks_path, 250, 100)),
+            "/v1/catalog/us/playlists/pl.1" => {
+                // The playlist includes the first page of its tracks
+                let mut playlist = common::playlist_json("pl.1", "Editorial");
+                let first_page: serde_json::Value =
+                    serde_json::from_str(&common::song_page(request, tracks_path, 250, 100))
+                        .unwrap();
+                playlist["relationships"] = serde_json::json!({ "tracks": first_page });
+                (200, serde_json::json!({ "data": [playlist] }).to_string())
+            }
+            _ => (404, String::new()),
+        }
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let playlist = client.get_playlist_with_tracks("pl.1").await.unwrap();
+    let tracks = playlist.relationships.unwrap().tracks.unwrap();
+    assert_eq!(tracks.data.len(), 250);
+    assert_eq!(tracks.data[249].id, "250");
+    assert!(tracks.next.is_none());
+    assert_eq!(server.requests().len(), 3);
+
+    let tracks = client.get_playlist_tracks("pl.1").await.unwrap();
+    assert_eq!(tracks.len(), 250);
+
+    let streamed: Vec<_> = client
+        .stream_playlist_tracks("pl.1")
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(streamed.len(), 250);
+    assert_eq!(streamed[100].id, "101");
+
+    // Pages are only requested as the stream is consumed
+    let before = server.requests().len();
+    let first: Vec<_> = client
+        .stream_playlist_tracks("pl.1")
+        .take(5)
+        .collect()
+        .await;
+    assert_eq!(first.len(), 5);
+    assert_eq!(server.requests().len(), before + 1);
+
+    let mut invalid = client.stream_playlist_tracks("bad id!");
+    assert!(invalid.next().await.unwrap().is_err());
+    assert!(invalid.next().await.is_none());
+}
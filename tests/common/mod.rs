@@ -0,0 +1,337 @@
+//! Shared helpers for tests that talk to a local stand-in for the Apple Music API
+
+#![allow(dead_code)]
+
+use apple_music_api::config::ClientConfigBuilder;
+use apple_music_api::ClientConfig;
+use p256::ecdsa::SigningKey;
+use p256::elliptic_curve::rand_core::OsRng;
+use p256::pkcs8::EncodePrivateKey;
+use pem::Pem;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query, e.g. `/v1/catalog/us/songs?ids=1`
+    pub target: String,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Get the path without the query string
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// Get the decoded query parameters
+    pub fn query(&self) -> Vec<(String, String)> {
+        url::Url::parse(&format!("http://localhost{}", self.target))
+            .unwrap()
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    /// Get the first value of a query parameter
+    pub fn query_param(&self, key: &str) -> Option<String> {
+        self.query()
+            .into_iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Get a header value
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+}
+
+type Handler = dyn Fn(&RecordedRequest) -> (u16, String) + Send + Sync;
+
+/// Minimal HTTP server answering every request through a handler
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    /// Start a server on a free localhost port
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&RecordedRequest) -> (u16, String) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    recorded.lock().unwrap().push(request.clone());
+                    let (status, body) = handler(&request);
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Get the base URL of the server
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get every request received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<RecordedRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buffer[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    Some(RecordedRequest {
+        method,
+        target,
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+/// Generate a random ES256 private key in PEM format
+pub fn generate_private_key() -> String {
+    let signing_key = SigningKey::random(&mut OsRng);
+    let private_key_bytes = signing_key.to_pkcs8_der().unwrap();
+    pem::encode(&Pem::new(
+        "PRIVATE KEY",
+        private_key_bytes.as_bytes().to_vec(),
+    ))
+}
+
+/// Build a configuration pointing at the given base URL
+pub fn mock_config(base_url: &str) -> ClientConfig {
+    ClientConfigBuilder::default()
+        .base_url(base_url.to_string())
+        .developer_token("developer-token".to_string())
+        .key_id("KEY_ID".to_string())
+        .team_id("TEAM_ID".to_string())
+        .build()
+        .unwrap()
+}
+
+/// JSON for a catalog album resource
+pub fn album_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "albums",
+        "href": format!("/v1/catalog/us/albums/{}", id),
+        "attributes": {
+            "name": name,
+            "artistName": "Artist",
+            "artwork": { "url": "https://example.com/{w}x{h}.jpg" },
+            "genreNames": ["Pop"],
+            "isComplete": true,
+            "isCompilation": false,
+            "isSingle": false,
+            "releaseDate": "2020-01-01T00:00:00Z",
+            "trackCount": 10,
+            "url": format!("https://music.apple.com/us/album/{}", id)
+        }
+    })
+}
+
+/// JSON for a catalog artist resource
+pub fn artist_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "artists",
+        "attributes": {
+            "name": name,
+            "genreNames": ["Pop"],
+            "url": format!("https://music.apple.com/us/artist/{}", id)
+        }
+    })
+}
+
+/// JSON for a catalog song resource
+pub fn song_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "songs",
+        "attributes": {
+            "albumName": "Album",
+            "artistName": "Artist",
+            "artwork": { "url": "https://example.com/{w}x{h}.jpg" },
+            "genreNames": ["Pop"],
+            "hasLyrics": false,
+            "isAppleDigitalMaster": false,
+            "isrc": format!("USAAA{:0>7}", id),
+            "name": name,
+            "previews": [],
+            "releaseDate": "2020-01-01T00:00:00Z",
+            "url": format!("https://music.apple.com/us/song/{}", id)
+        }
+    })
+}
+
+/// JSON for a catalog music video resource
+pub fn music_video_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "music-videos",
+        "attributes": {
+            "artistName": "Artist",
+            "artwork": { "url": "https://example.com/{w}x{h}.jpg" },
+            "genreNames": ["Pop"],
+            "has4K": false,
+            "hasHDR": false,
+            "isrc": format!("USAAA{:0>7}", id),
+            "name": name,
+            "previews": [],
+            "releaseDate": "2020-01-01T00:00:00Z",
+            "url": format!("https://music.apple.com/us/music-video/{}", id)
+        }
+    })
+}
+
+/// Serve `total` songs in pages of `page_size`, linked by `next`
+///
+/// `path` is the collection path; the song IDs are `"1"` to `total`.
+pub fn song_page(request: &RecordedRequest, path: &str, total: usize, page_size: usize) -> String {
+    let offset: usize = request
+        .query_param("offset")
+        .and_then(|offset| offset.parse().ok())
+        .unwrap_or(0);
+    let end = (offset + page_size).min(total);
+    let data: Vec<_> = (offset + 1..=end)
+        .map(|i| song_json(&i.to_string(), &format!("Track {}", i)))
+        .collect();
+    let mut body = serde_json::json!({ "data": data });
+    if end < total {
+        body["next"] = format!("{}?offset={}", path, end).into();
+    }
+    body.to_string()
+}
+
+/// JSON for a catalog playlist resource
+pub fn playlist_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "playlists",
+        "attributes": {
+            "curatorName": "Apple Music",
+            "lastModifiedDate": "2024-01-01T00:00:00Z",
+            "name": name,
+            "playlistType": "editorial"
+        }
+    })
+}
+
+/// JSON for a curator or Apple curator resource
+pub fn curator_json(id: &str, resource_type: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": resource_type,
+        "attributes": {
+            "name": name,
+            "url": format!("https://music.apple.com/us/curator/{}", id)
+        }
+    })
+}
+
+/// JSON for a genre resource
+pub fn genre_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "genres",
+        "attributes": { "name": name, "parentId": "34", "parentName": "Music" }
+    })
+}
+
+/// JSON for a station resource
+pub fn station_json(id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "stations",
+        "attributes": {
+            "artwork": { "url": "https://example.com/{w}x{h}.jpg" },
+            "isLive": false,
+            "name": name,
+            "url": format!("https://music.apple.com/us/station/{}", id),
+            "mediaKind": "audio"
+        }
+    })
+}
+
+/// JSON for a storefront resource
+pub fn storefront_json(id: &str, default_language: &str, languages: &[&str]) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "storefronts",
+        "attributes": {
+            "name": id.to_uppercase(),
+            "defaultLanguageTag": default_language,
+            "explicitContentPolicy": "allowed",
+            "supportedLanguageTags": languages
+        }
+    })
+}
@@ -0,0 +1,226 @@
+//! Tests for credential stores
+
+mod common;
+
+use apple_music_api::credentials::{StoredToken, TokenKind};
+use apple_music_api::{
+    AppleMusicClient, ClientConfig, CredentialStore, FileCredentialStore, MemoryCredentialStore,
+};
+use chrono::{Duration, Utc};
+use common::generate_private_key;
+use std::sync::Arc;
+
+/// Test saving, loading and clearing tokens in memory
+#[test]
+fn test_memory_store_roundtrip() {
+    let store = MemoryCredentialStore::new();
+    assert!(store.load(TokenKind::User).unwrap().is_none());
+
+    let token = StoredToken::new("user-token", Utc::now(), None);
+    store.save(TokenKind::User, &token).unwrap();
+    assert_eq!(store.load(TokenKind::User).unwrap(), Some(token));
+    assert!(store.load(TokenKind::Developer).unwrap().is_none());
+
+    store.clear(TokenKind::User).unwrap();
+    assert!(store.load(TokenKind::User).unwrap().is_none());
+}
+
+/// Test that the file store persists tokens with owner-only permissions
+#[test]
+fn test_file_store_persists_tokens() {
+    let path = std::env::temp_dir().join(format!("apple-music-{}.json", uuid::Uuid::new_v4()));
+    let issued_at = Utc::now();
+    let token = StoredToken::new("user-token", issued_at, Some(issued_at + Duration::days(1)));
+
+    FileCredentialStore::new(&path)
+        .save(TokenKind::User, &token)
+        .unwrap();
+
+    let reopened = FileCredentialStore::new(&path);
+    assert_eq!(reopened.load(TokenKind::User).unwrap(), Some(token));
+    assert!(reopened.load(TokenKind::Developer).unwrap().is_none());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let contents: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(contents["user"]["token"], "user-token");
+    assert!(contents["user"]["issued_at"].is_string());
+    assert!(contents["user"]["expires_at"].is_string());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Test that a stored developer token is reused instead of minted again
+#[tokio::test]
+async fn test_config_reuses_stored_tokens() {
+    let store = Arc::new(MemoryCredentialStore::new());
+    let private_key = generate_private_key();
+
+    let first =
+        ClientConfig::from_credential_store("KEY_ID", "TEAM_ID", &private_key, store.as_ref())
+            .unwrap();
+    let stored = store.load(TokenKind::Developer).unwrap().unwrap();
+    assert_eq!(stored.token.expose(), first.developer_token.expose());
+    assert!(stored.expires_at.unwrap() > stored.issued_at);
+
+    let second =
+        ClientConfig::from_credential_store("KEY_ID", "TEAM_ID", &private_key, store.as_ref())
+            .unwrap();
+    assert_eq!(second.developer_token, first.developer_token);
+
+    // A captured user token survives into the next client
+    let mut client = AppleMusicClient::with_credential_store(second, store.clone())
+        .await
+        .unwrap();
+    client
+        .set_user_token(Some("user-token".to_string()))
+        .await
+        .unwrap();
+
+    let third =
+        ClientConfig::from_credential_store("KEY_ID", "TEAM_ID", &private_key, store.as_ref())
+            .unwrap();
+    assert_eq!(third.user_token.unwrap().expose(), "user-token");
+}
+
+/// Test that a developer token signed with another key or team is replaced
+#[test]
+fn test_stored_developer_token_from_other_key_is_replaced() {
+    let store = MemoryCredentialStore::new();
+    let private_key = generate_private_key();
+
+    let first =
+        ClientConfig::from_credential_store("OLD_KEY", "TEAM_ID", &private_key, &store).unwrap();
+    let stored = store.load(TokenKind::Developer).unwrap().unwrap();
+    assert!(stored.is_signed_by("OLD_KEY", "TEAM_ID"));
+    assert!(!stored.is_signed_by("NEW_KEY", "TEAM_ID"));
+    assert!(!stored.is_signed_by("OLD_KEY", "OTHER_TEAM"));
+
+    // The key was rotated, so the stored token must not be reused
+    let rotated =
+        ClientConfig::from_credential_store("NEW_KEY", "TEAM_ID", &private_key, &store).unwrap();
+    assert_ne!(rotated.developer_token, first.developer_token);
+    let stored = store.load(TokenKind::Developer).unwrap().unwrap();
+    assert!(stored.is_signed_by("NEW_KEY", "TEAM_ID"));
+
+    let moved =
+        ClientConfig::from_credential_store("NEW_KEY", "OTHER_TEAM", &private_key, &store).unwrap();
+    assert_ne!(moved.developer_token, rotated.developer_token);
+
+    assert!(!StoredToken::new("not-a-jwt", Utc::now(), None).is_signed_by("NEW_KEY", "TEAM_ID"));
+}
+
+/// Test that a developer token that can't be stored is reported
+#[tokio::test]
+async fn test_client_store_rejects_non_jwt_developer_token() {
+    let store = Arc::new(MemoryCredentialStore::new());
+    let config = common::mock_config("http://127.0.0.1:9");
+
+    let result = AppleMusicClient::with_credential_store(config, store.clone()).await;
+    assert!(result.is_err());
+    assert!(store.load(TokenKind::Developer).unwrap().is_none());
+}
+
+/// Test that stored token expiry is checked against the given clock
+#[test]
+fn test_config_from_store_uses_clock() {
+    use apple_music_api::clock::{Clock, MockClock, SharedClock};
+
+    let store = MemoryCredentialStore::new();
+    let private_key = generate_private_key();
+    let clock = MockClock::new(std::time::SystemTime::now());
+    let load = || {
+        ClientConfig::from_credential_store_with_clock(
+            "KEY_ID",
+            "TEAM_ID",
+            &private_key,
+            &store,
+            SharedClock::new(clock.clone()),
+        )
+        .unwrap()
+    };
+
+    let issued = clock.now_utc();
+    store
+        .save(
+            TokenKind::User,
+            &StoredToken::new("user-token", issued, Some(issued + Duration::days(30))),
+        )
+        .unwrap();
+
+    let first = load();
+    assert_eq!(first.clock.now(), clock.now());
+    assert_eq!(first.user_token.unwrap().expose(), "user-token");
+
+    clock.advance(std::time::Duration::from_secs(31 * 86_400));
+    let second = load();
+    assert_eq!(second.developer_token, first.developer_token);
+    assert!(second.user_token.is_none());
+
+    // Within a day of expiry the stored developer token is re-signed
+    clock.advance(std::time::Duration::from_secs(149 * 86_400));
+    let third = load();
+    assert_ne!(third.developer_token, first.developer_token);
+}
+
+/// Test that a JWT client saves every developer token it signs to its store
+#[tokio::test]
+async fn test_jwt_client_saves_refreshed_developer_token() {
+    use apple_music_api::clock::{MockClock, SharedClock};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let server = common::MockServer::start(|_| (200, r#"{"data":[]}"#.to_string())).await;
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let mut config = common::mock_config(server.url());
+    config.clock = SharedClock::new(clock.clone());
+    let store = Arc::new(MemoryCredentialStore::new());
+    store
+        .save(
+            TokenKind::User,
+            &StoredToken::new("stored-user", Utc::now(), None),
+        )
+        .unwrap();
+
+    let client = AppleMusicClient::with_jwt_auth_and_credential_store(
+        config,
+        "TEAM_ID".to_string(),
+        "KEY_ID".to_string(),
+        generate_private_key(),
+        store.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(client.user_token().await.as_deref(), Some("stored-user"));
+
+    let stored = |kind| {
+        store
+            .load(kind)
+            .unwrap()
+            .unwrap()
+            .token
+            .expose()
+            .to_string()
+    };
+    let first = stored(TokenKind::Developer);
+    client.get_storefronts().await.unwrap();
+    assert_eq!(
+        server.requests()[0].header("authorization").unwrap(),
+        format!("Bearer {}", first)
+    );
+
+    // A token signed again on the request path replaces the stored one
+    clock.advance(Duration::from_secs(15_777_000 - 86_400 + 1));
+    client.get_storefronts().await.unwrap();
+    let refreshed = stored(TokenKind::Developer);
+    assert_ne!(refreshed, first);
+    assert_eq!(
+        server.requests()[1].header("authorization").unwrap(),
+        format!("Bearer {}", refreshed)
+    );
+}
@@ -1,8 +1,9 @@
 //! Integration tests for the Apple Music API client
 //!
 //! These tests require valid API credentials to run.
-//! Set the following environment variables:
-//! - APPLE_MUSIC_DEVELOPER_TOKEN: Your Apple Music developer token
+//! Set the environment variables read by `ClientConfig::from_env`, e.g.:
+//! - APPLE_MUSIC_TEAM_ID, APPLE_MUSIC_KEY_ID and APPLE_MUSIC_PRIVATE_KEY
+//!   (or APPLE_MUSIC_DEVELOPER_TOKEN)
 //! - APPLE_MUSIC_USER_TOKEN: Your Apple Music user token (optional)
 
 use apple_music_api::config::MediaType;
@@ -58,12 +59,7 @@ fn test_resource_id_validation() {
 #[tokio::test]
 #[ignore]
 async fn test_real_api_search() {
-    let team_id = std::env::var("APPLE_MUSIC_TEAM_ID").expect("APPLE_MUSIC_TEAM_ID must be set");
-    let key_id = std::env::var("APPLE_MUSIC_KEY_ID").expect("APPLE_MUSIC_KEY_ID must be set");
-    let private_key =
-        std::env::var("APPLE_MUSIC_PRIVATE_KEY").expect("APPLE_MUSIC_PRIVATE_KEY must be set");
-
-    let config = ClientConfig::new(team_id, key_id, private_key).unwrap();
+    let config = ClientConfig::from_env().unwrap();
     let client = AppleMusicClient::new(config).await.unwrap();
 
     // Test search
@@ -80,12 +76,7 @@ async fn test_real_api_search() {
 #[tokio::test]
 #[ignore]
 async fn test_real_api_storefront() {
-    let team_id = std::env::var("APPLE_MUSIC_TEAM_ID").expect("APPLE_MUSIC_TEAM_ID must be set");
-    let key_id = std::env::var("APPLE_MUSIC_KEY_ID").expect("APPLE_MUSIC_KEY_ID must be set");
-    let private_key =
-        std::env::var("APPLE_MUSIC_PRIVATE_KEY").expect("APPLE_MUSIC_PRIVATE_KEY must be set");
-
-    let config = ClientConfig::new(team_id, key_id, private_key).unwrap();
+    let config = ClientConfig::from_env().unwrap();
     let client = AppleMusicClient::new(config).await.unwrap();
 
     // Test storefront retrieval
@@ -0,0 +1,117 @@
+//! Tests for the storefront registry
+
+mod common;
+
+use apple_music_api::{AppleMusicClient, AppleMusicError, ClientConfig, StorefrontRegistry};
+use common::MockServer;
+
+/// Test lookups and suggestions in the bundled registry
+#[test]
+fn test_bundled_registry() {
+    let registry = StorefrontRegistry::bundled();
+    assert!(registry.len() > 150);
+    assert_eq!(registry.get("CA").unwrap().name, "Canada");
+    assert_eq!(registry.validate(" FR ").unwrap(), "fr");
+
+    let error = registry.validate("uss").unwrap_err().to_string();
+    assert!(error.contains("did you mean \"us\""), "{}", error);
+    assert_eq!(registry.suggest("germny").unwrap().id, "de");
+    assert!(registry.suggest("zzzzzzzz").is_none());
+}
+
+/// Test that configurations are validated against the registry
+#[test]
+fn test_config_validation_uses_registry() {
+    let mut config = common::mock_config("http://localhost");
+    config.storefront = "uss".to_string();
+    let error = config.validate().unwrap_err().to_string();
+    assert!(error.contains("did you mean \"us\""), "{}", error);
+
+    // A refreshed registry can accept storefronts missing from the bundled list
+    config.storefront = "zz".to_string();
+    assert!(config.validate().is_err());
+    config.storefront_registry = Some(StorefrontRegistry::from_storefronts(&[
+        serde_json::from_value(common::storefront_json("zz", "en-US", &["en-US"])).unwrap(),
+    ]));
+    assert!(config.validate().is_ok());
+
+    // Opting out of the registry only checks the format
+    config.storefront_registry = None;
+    config.storefront_format_only = true;
+    assert!(config.validate().is_ok());
+    config.storefront = "u5".to_string();
+    assert!(config.validate().is_err());
+
+    let error = ClientConfig::from_env_vars([
+        ("APPLE_MUSIC_DEVELOPER_TOKEN", "developer-token"),
+        ("APPLE_MUSIC_STOREFRONT", "uss"),
+    ])
+    .unwrap_err()
+    .to_string();
+    assert!(error.contains("APPLE_MUSIC_STOREFRONT"), "{}", error);
+    assert!(error.contains("did you mean \"us\""), "{}", error);
+}
+
+/// Test refreshing the registry across every page of storefronts
+#[tokio::test]
+async fn test_refresh_registry_follows_pages() {
+    let server = MockServer::start(|request| {
+        let body = match request.query_param("offset").as_deref() {
+            None => serde_json::json!({
+                "data": [common::storefront_json("us", "en-US", &["en-US"])],
+                "next": "/v1/storefronts?offset=1"
+            }),
+            Some("1") => serde_json::json!({
+                "data": [common::storefront_json("zz", "en-GB", &["en-GB"])]
+            }),
+            Some(_) => return (404, String::new()),
+        };
+        (200, body.to_string())
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let registry = client.refresh_storefront_registry().await.unwrap();
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.get("zz").unwrap().default_language, "en-GB");
+    assert_eq!(server.requests().len(), 2);
+}
+
+/// Test that a `next` link pointing back to an earlier page is reported
+#[tokio::test]
+async fn test_pagination_loop_is_an_error() {
+    use futures_util::TryStreamExt;
+
+    let server = MockServer::start(|request| {
+        let next = format!("{}?offset=1", request.path());
+        let data = if request.path().ends_with("/tracks") {
+            serde_json::json!([common::song_json("1", "Song")])
+        } else {
+            serde_json::json!([common::storefront_json("us", "en-US", &["en-US"])])
+        };
+        (
+            200,
+            serde_json::json!({ "data": data, "next": next }).to_string(),
+        )
+    })
+    .await;
+    let client = AppleMusicClient::new(common::mock_config(server.url()))
+        .await
+        .unwrap();
+
+    let error = client.get_storefronts().await.unwrap_err();
+    assert!(matches!(error, AppleMusicError::Pagination(_)), "{}", error);
+    assert_eq!(error.status_code(), None);
+    assert_eq!(server.requests().len(), 2);
+
+    let error = client
+        .stream_playlist_tracks("pl.1")
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("loops back"), "{}", error);
+    assert_eq!(server.requests().len(), 4);
+}
@@ -0,0 +1,76 @@
+//! Tests for the MusicKit JS user token server
+//!
+//! A local stand-in client plays the part of the browser page.
+
+#![cfg(feature = "user-token-server")]
+
+use apple_music_api::user_token_server::CALLBACK_PATH;
+use apple_music_api::{AppleMusicError, UserTokenServer};
+use std::time::Duration;
+
+/// Test that a posted token is returned to the caller
+#[tokio::test]
+async fn test_receives_posted_token() {
+    let server = UserTokenServer::bind("fake-developer-token", 0)
+        .await
+        .unwrap();
+    let url = server.url().unwrap();
+
+    let waiter = tokio::spawn(server.wait_for_token(Duration::from_secs(5)));
+
+    // A connection that never sends its request doesn't block the others
+    let _idle = tokio::net::TcpStream::connect(server_addr(&url))
+        .await
+        .unwrap();
+
+    let http = reqwest::Client::new();
+    let page = http.get(&url).send().await.unwrap().text().await.unwrap();
+    assert!(page.contains("\"fake-developer-token\""));
+    assert!(page.contains("MusicKit.configure"));
+    let state = page
+        .split("state: \"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap();
+    let callback = format!("{}{}", url.trim_end_matches('/'), CALLBACK_PATH);
+
+    // Invalid bodies are rejected without stopping the server
+    let rejected = http.post(&callback).body("not json").send().await.unwrap();
+    assert_eq!(rejected.status(), 400);
+
+    // Callbacks that don't echo the page's state are rejected
+    for body in [
+        serde_json::json!({ "musicUserToken": "forged-token" }),
+        serde_json::json!({ "musicUserToken": "forged-token", "state": "other" }),
+    ] {
+        let rejected = http.post(&callback).json(&body).send().await.unwrap();
+        assert_eq!(rejected.status(), 403);
+    }
+
+    let accepted = http
+        .post(&callback)
+        .json(&serde_json::json!({ "musicUserToken": "fake-user-token", "state": state }))
+        .send()
+        .await
+        .unwrap();
+    assert!(accepted.status().is_success());
+
+    let token = waiter.await.unwrap().unwrap();
+    assert_eq!(token, "fake-user-token");
+}
+
+/// Test that waiting without a callback times out
+#[tokio::test]
+async fn test_wait_for_token_timeout() {
+    let server = UserTokenServer::bind("fake-developer-token", 0)
+        .await
+        .unwrap();
+
+    let result = server.wait_for_token(Duration::from_millis(50)).await;
+    assert!(matches!(result, Err(AppleMusicError::Timeout(_))));
+}
+
+/// Get the `host:port` part of a server URL
+fn server_addr(url: &str) -> &str {
+    url.trim_start_matches("http://").trim_end_matches('/')
+}